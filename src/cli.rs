@@ -0,0 +1,537 @@
+use anyhow::{anyhow, Result};
+
+use crate::models::Status;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Interactive,
+    Schema,
+    Diff {
+        old: String,
+        new: String,
+    },
+    Export {
+        format: String,
+    },
+    Repair,
+    Standup,
+    Doctor {
+        fix: bool,
+        json: bool,
+    },
+    Reset {
+        keep_settings: bool,
+    },
+    Import {
+        epic_id: u32,
+        path: String,
+    },
+    Project {
+        name: Option<String>,
+        version: Option<String>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub command: Command,
+    pub quiet: bool,
+    pub done_statuses: Option<Vec<Status>>,
+    pub compact: bool,
+    pub batch: bool,
+    pub delta: bool,
+    pub strict_delete: bool,
+    pub resolve_on_complete: bool,
+    pub strict_dod: bool,
+    pub confirm_default: Option<bool>,
+    pub emoji: bool,
+    pub history: bool,
+    pub show_snoozed: bool,
+    pub no_wizard: bool,
+    pub exe_relative: bool,
+    pub read_only: bool,
+    pub bell: bool,
+    pub id_start: Option<u32>,
+    pub db_path: Option<String>,
+    pub log_path: Option<String>,
+    pub watch_epic: Option<u32>,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut parsed = Self {
+            command: Command::Interactive,
+            quiet: false,
+            done_statuses: None,
+            compact: false,
+            batch: false,
+            delta: false,
+            strict_delete: false,
+            resolve_on_complete: false,
+            strict_dod: false,
+            confirm_default: None,
+            emoji: false,
+            history: false,
+            show_snoozed: false,
+            no_wizard: false,
+            exe_relative: false,
+            read_only: false,
+            bell: false,
+            id_start: None,
+            db_path: None,
+            log_path: None,
+            watch_epic: None,
+        };
+
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "schema" if parsed.command == Command::Interactive => {
+                    parsed.command = Command::Schema
+                }
+                "diff" if parsed.command == Command::Interactive => {
+                    let mut path = || {
+                        args.next()
+                            .ok_or_else(|| anyhow!("diff expects two board files"))
+                    };
+                    parsed.command = Command::Diff {
+                        old: path()?,
+                        new: path()?,
+                    };
+                }
+                "import" if parsed.command == Command::Interactive => {
+                    let epic_id = args
+                        .next()
+                        .and_then(|value| value.parse::<u32>().ok())
+                        .ok_or_else(|| anyhow!("import expects an epic id and a CSV file"))?;
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("import expects an epic id and a CSV file"))?;
+                    parsed.command = Command::Import { epic_id, path };
+                }
+                "project" if parsed.command == Command::Interactive => {
+                    let name = args
+                        .next()
+                        .ok_or_else(|| anyhow!("project expects a name and an optional version"))?;
+                    let version = args.next_if(|value| !value.starts_with('-'));
+                    let label = |value: String| Some(value).filter(|value| !value.is_empty());
+                    parsed.command = Command::Project {
+                        name: label(name),
+                        version: version.and_then(label),
+                    };
+                }
+                "reset" if parsed.command == Command::Interactive => {
+                    parsed.command = Command::Reset {
+                        keep_settings: false,
+                    }
+                }
+                "--keep-settings" => match &mut parsed.command {
+                    Command::Reset { keep_settings } => *keep_settings = true,
+                    _ => return Err(anyhow!("--keep-settings is only valid for reset")),
+                },
+                "repair" if parsed.command == Command::Interactive => {
+                    parsed.command = Command::Repair
+                }
+                "standup" if parsed.command == Command::Interactive => {
+                    parsed.command = Command::Standup
+                }
+                "doctor" if parsed.command == Command::Interactive => {
+                    parsed.command = Command::Doctor {
+                        fix: false,
+                        json: false,
+                    }
+                }
+                "--fix" => match &mut parsed.command {
+                    Command::Doctor { fix, .. } => *fix = true,
+                    _ => return Err(anyhow!("--fix is only valid for doctor")),
+                },
+                "--json" => match &mut parsed.command {
+                    Command::Doctor { json, .. } => *json = true,
+                    _ => return Err(anyhow!("--json is only valid for doctor")),
+                },
+                "export" if parsed.command == Command::Interactive => {
+                    parsed.command = Command::Export {
+                        format: "json".to_owned(),
+                    }
+                }
+                "--format" => match &mut parsed.command {
+                    Command::Export { format } => {
+                        *format = args
+                            .next()
+                            .ok_or_else(|| anyhow!("--format expects a format name"))?
+                    }
+                    _ => return Err(anyhow!("--format is only valid for export")),
+                },
+                "-q" | "--quiet" => parsed.quiet = true,
+                "--compact" => parsed.compact = true,
+                "--batch" => parsed.batch = true,
+                "--delta" => parsed.delta = true,
+                "--strict-delete" => parsed.strict_delete = true,
+                "--resolve-on-complete" => parsed.resolve_on_complete = true,
+                "--strict-dod" => parsed.strict_dod = true,
+                "--emoji" => parsed.emoji = true,
+                "--history" => parsed.history = true,
+                "--show-snoozed" => parsed.show_snoozed = true,
+                "--no-wizard" => parsed.no_wizard = true,
+                "--exe-relative" => parsed.exe_relative = true,
+                "--read-only" => parsed.read_only = true,
+                "--bell" => parsed.bell = true,
+                "--confirm-default" => {
+                    let value = args.next().unwrap_or_default();
+                    parsed.confirm_default = Some(
+                        parse_yes_no(&value)
+                            .ok_or_else(|| anyhow!("--confirm-default expects yes or no"))?,
+                    );
+                }
+                "--db" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--db expects a path to the board file"))?;
+                    parsed.db_path = Some(value);
+                }
+                "--log" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--log expects a path to the log file"))?;
+                    parsed.log_path = Some(value);
+                }
+                "--watch-epic" => {
+                    let value = args.next().unwrap_or_default();
+                    parsed.watch_epic = Some(
+                        value
+                            .parse::<u32>()
+                            .map_err(|_| anyhow!("--watch-epic expects an epic id"))?,
+                    );
+                }
+                "--id-start" => {
+                    let value = args.next().unwrap_or_default();
+                    parsed.id_start = Some(
+                        value
+                            .parse::<u32>()
+                            .map_err(|_| anyhow!("--id-start expects a number"))?,
+                    );
+                }
+                "--done" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--done expects a comma separated status list"))?;
+                    parsed.done_statuses = Some(parse_status_list(&value)?);
+                }
+                other => return Err(anyhow!("Unknown argument: {}", other)),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+pub fn parse_yes_no(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" | "true" => Some(true),
+        "n" | "no" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_status_list(value: &str) -> Result<Vec<Status>> {
+    value
+        .split(',')
+        .map(|status| status.parse::<Status>().map_err(|error| anyhow!(error)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parse_should_default_to_interactive() {
+        assert_eq!(parse(&[]).unwrap().command, Command::Interactive);
+    }
+
+    #[test]
+    fn parse_should_recognize_schema_command() {
+        assert_eq!(parse(&["schema"]).unwrap().command, Command::Schema);
+    }
+
+    #[test]
+    fn parse_should_recognize_diff_command() {
+        assert_eq!(
+            parse(&["diff", "old.json", "new.json"]).unwrap().command,
+            Command::Diff {
+                old: "old.json".to_owned(),
+                new: "new.json".to_owned(),
+            }
+        );
+        assert!(parse(&["diff", "old.json"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_recognize_export_command() {
+        assert_eq!(
+            parse(&["export"]).unwrap().command,
+            Command::Export {
+                format: "json".to_owned()
+            }
+        );
+        assert_eq!(
+            parse(&["export", "--format", "csv"]).unwrap().command,
+            Command::Export {
+                format: "csv".to_owned()
+            }
+        );
+        assert!(parse(&["export", "--format"]).is_err());
+        assert!(parse(&["--format", "csv"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_recognize_import_command() {
+        assert_eq!(
+            parse(&["import", "3", "stories.csv"]).unwrap().command,
+            Command::Import {
+                epic_id: 3,
+                path: "stories.csv".to_owned(),
+            }
+        );
+        assert!(parse(&["import", "stories.csv"]).is_err());
+        assert!(parse(&["import", "3"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_recognize_project_command() {
+        assert_eq!(
+            parse(&["project", "jirrah", "0.2", "--quiet"]).unwrap(),
+            Args {
+                command: Command::Project {
+                    name: Some("jirrah".to_owned()),
+                    version: Some("0.2".to_owned()),
+                },
+                quiet: true,
+                ..parse(&[]).unwrap()
+            }
+        );
+        assert_eq!(
+            parse(&["project", ""]).unwrap().command,
+            Command::Project {
+                name: None,
+                version: None,
+            }
+        );
+        assert!(parse(&["project"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_recognize_reset_command() {
+        assert_eq!(
+            parse(&["reset"]).unwrap().command,
+            Command::Reset {
+                keep_settings: false
+            }
+        );
+        assert_eq!(
+            parse(&["reset", "--keep-settings"]).unwrap().command,
+            Command::Reset {
+                keep_settings: true
+            }
+        );
+        assert!(parse(&["--keep-settings"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_recognize_repair_command() {
+        assert_eq!(parse(&["repair"]).unwrap().command, Command::Repair);
+    }
+
+    #[test]
+    fn parse_should_recognize_standup_command() {
+        assert_eq!(parse(&["standup"]).unwrap().command, Command::Standup);
+    }
+
+    #[test]
+    fn parse_should_read_doctor_flags() {
+        assert_eq!(
+            parse(&["doctor"]).unwrap().command,
+            Command::Doctor {
+                fix: false,
+                json: false,
+            }
+        );
+        assert_eq!(
+            parse(&["doctor", "--json", "--fix"]).unwrap().command,
+            Command::Doctor {
+                fix: true,
+                json: true,
+            }
+        );
+        assert!(parse(&["--fix"]).is_err());
+        assert!(parse(&["export", "--json"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_recognize_quiet_flag() {
+        assert!(!parse(&[]).unwrap().quiet);
+        assert!(parse(&["--quiet"]).unwrap().quiet);
+        assert!(parse(&["-q"]).unwrap().quiet);
+    }
+
+    #[test]
+    fn parse_should_recognize_compact_flag() {
+        assert!(!parse(&[]).unwrap().compact);
+        assert!(parse(&["--compact"]).unwrap().compact);
+    }
+
+    #[test]
+    fn parse_should_recognize_batch_flag() {
+        assert!(!parse(&[]).unwrap().batch);
+        assert!(parse(&["--batch"]).unwrap().batch);
+    }
+
+    #[test]
+    fn parse_should_recognize_delta_flag() {
+        assert!(!parse(&[]).unwrap().delta);
+        assert!(parse(&["--delta"]).unwrap().delta);
+    }
+
+    #[test]
+    fn parse_should_recognize_strict_delete_flag() {
+        assert!(!parse(&[]).unwrap().strict_delete);
+        assert!(parse(&["--strict-delete"]).unwrap().strict_delete);
+    }
+
+    #[test]
+    fn parse_should_recognize_resolve_on_complete_flag() {
+        assert!(!parse(&[]).unwrap().resolve_on_complete);
+        assert!(
+            parse(&["--resolve-on-complete"])
+                .unwrap()
+                .resolve_on_complete
+        );
+    }
+
+    #[test]
+    fn parse_should_recognize_strict_dod_flag() {
+        assert!(!parse(&[]).unwrap().strict_dod);
+        assert!(parse(&["--strict-dod"]).unwrap().strict_dod);
+    }
+
+    #[test]
+    fn parse_should_recognize_emoji_flag() {
+        assert!(!parse(&[]).unwrap().emoji);
+        assert!(parse(&["--emoji"]).unwrap().emoji);
+    }
+
+    #[test]
+    fn parse_should_recognize_history_flag() {
+        assert!(!parse(&[]).unwrap().history);
+        assert!(parse(&["--history"]).unwrap().history);
+    }
+
+    #[test]
+    fn parse_should_recognize_show_snoozed_flag() {
+        assert!(!parse(&[]).unwrap().show_snoozed);
+        assert!(parse(&["--show-snoozed"]).unwrap().show_snoozed);
+    }
+
+    #[test]
+    fn parse_should_recognize_no_wizard_flag() {
+        assert!(!parse(&[]).unwrap().no_wizard);
+        assert!(parse(&["--no-wizard"]).unwrap().no_wizard);
+    }
+
+    #[test]
+    fn parse_should_recognize_exe_relative_flag() {
+        assert!(!parse(&[]).unwrap().exe_relative);
+        assert!(parse(&["--exe-relative"]).unwrap().exe_relative);
+    }
+
+    #[test]
+    fn parse_should_recognize_bell_flag() {
+        assert!(!parse(&[]).unwrap().bell);
+        assert!(
+            parse(&["import", "1", "stories.csv", "--bell"])
+                .unwrap()
+                .bell
+        );
+    }
+
+    #[test]
+    fn parse_should_recognize_read_only_flag() {
+        assert!(!parse(&[]).unwrap().read_only);
+        assert!(parse(&["--read-only", "export"]).unwrap().read_only);
+    }
+
+    #[test]
+    fn parse_should_read_confirm_default() {
+        assert_eq!(parse(&[]).unwrap().confirm_default, None);
+        assert_eq!(
+            parse(&["--confirm-default", "yes"])
+                .unwrap()
+                .confirm_default,
+            Some(true)
+        );
+        assert_eq!(
+            parse(&["--confirm-default", "No"]).unwrap().confirm_default,
+            Some(false)
+        );
+        assert!(parse(&["--confirm-default"]).is_err());
+        assert!(parse(&["--confirm-default", "maybe"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_db_path() {
+        assert_eq!(parse(&[]).unwrap().db_path, None);
+        assert_eq!(
+            parse(&["--db", "board.json"]).unwrap().db_path,
+            Some("board.json".to_owned())
+        );
+        assert!(parse(&["--db"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_log_path() {
+        assert_eq!(parse(&[]).unwrap().log_path, None);
+        assert_eq!(
+            parse(&["--log", "ops.jsonl"]).unwrap().log_path,
+            Some("ops.jsonl".to_owned())
+        );
+        assert!(parse(&["--log"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_watch_epic() {
+        assert_eq!(parse(&[]).unwrap().watch_epic, None);
+        assert_eq!(parse(&["--watch-epic", "3"]).unwrap().watch_epic, Some(3));
+        assert!(parse(&["--watch-epic", "three"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_id_start() {
+        assert_eq!(parse(&[]).unwrap().id_start, None);
+        assert_eq!(parse(&["--id-start", "1000"]).unwrap().id_start, Some(1000));
+        assert!(parse(&["--id-start"]).is_err());
+        assert!(parse(&["--id-start", "big"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_read_done_statuses() {
+        assert_eq!(parse(&[]).unwrap().done_statuses, None);
+        assert_eq!(
+            parse(&["--done", "closed,in-progress"])
+                .unwrap()
+                .done_statuses,
+            Some(vec![Status::Closed, Status::InProgress])
+        );
+        assert!(parse(&["--done"]).is_err());
+        assert!(parse(&["--done", "closed,done"]).is_err());
+    }
+
+    #[test]
+    fn parse_should_error_on_unknown_arguments() {
+        assert!(parse(&["unknown"]).is_err());
+        assert!(parse(&["schema", "schema"]).is_err());
+    }
+}
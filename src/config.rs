@@ -0,0 +1,261 @@
+use std::{
+    io::BufRead,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli::{parse_yes_no, Args},
+    io_utils::read_line_lossy,
+    models::Status,
+    ui::Keymap,
+};
+
+const CONFIG_FILE_NAME: &str = "jirrah.toml";
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub db_path: Option<String>,
+    pub page_size: Option<usize>,
+    pub stale_days: Option<u32>,
+    pub progress_width: Option<usize>,
+    pub done_statuses: Option<Vec<Status>>,
+    pub emoji: Option<bool>,
+    pub history: Option<bool>,
+    pub show_snoozed: Option<bool>,
+    pub exe_relative: Option<bool>,
+    pub compact: Option<bool>,
+    pub batch: Option<bool>,
+    pub delta: Option<bool>,
+    pub strict_delete: Option<bool>,
+    pub resolve_on_complete: Option<bool>,
+    pub strict_dod: Option<bool>,
+    pub confirm_default: Option<bool>,
+    pub keys: Option<Keymap>,
+}
+
+impl Config {
+    fn candidates() -> Vec<PathBuf> {
+        let home = std::env::var("HOME").ok().map(PathBuf::from);
+        [Some(PathBuf::from(".")), home]
+            .into_iter()
+            .flatten()
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .collect()
+    }
+
+    pub fn exists() -> bool {
+        Self::candidates().iter().any(|path| path.exists())
+    }
+
+    pub fn user_path() -> PathBuf {
+        std::env::var("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(CONFIG_FILE_NAME)
+    }
+
+    pub fn load() -> Result<Self> {
+        for path in Self::candidates() {
+            if path.exists() {
+                let content = std::fs::read_to_string(&path)?;
+                return Self::parse(&content)
+                    .map_err(|error| anyhow!("{}: {}", path.display(), error));
+            }
+        }
+
+        Ok(Self::default())
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)?;
+        if let Some(keys) = &config.keys {
+            keys.validate()?;
+        }
+
+        Ok(config)
+    }
+
+    pub fn from_wizard(input: &mut impl BufRead) -> Self {
+        let mut ask = |question: &str| {
+            println!("{}", question);
+            read_line_lossy(input).trim().to_owned()
+        };
+
+        let data_dir = ask("Where should the board be stored? [./data]");
+        let data_dir = match data_dir.is_empty() {
+            true => "./data",
+            false => data_dir.trim_end_matches('/'),
+        };
+        let emoji = ask("Show emoji status icons? [y/N]");
+        let page_size = ask("Description lines per screen? [fit to terminal]");
+
+        Self {
+            db_path: Some(format!("{}/db.json", data_dir)),
+            page_size: page_size.parse().ok().filter(|size| *size > 0),
+            emoji: Some(parse_yes_no(&emoji).unwrap_or(false)),
+            ..Self::default()
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn apply(&self, mut args: Args) -> Args {
+        args.db_path = args.db_path.or_else(|| self.db_path.clone());
+        args.done_statuses = args.done_statuses.or_else(|| self.done_statuses.clone());
+        args.confirm_default = args.confirm_default.or(self.confirm_default);
+        args.emoji |= self.emoji.unwrap_or(false);
+        args.history |= self.history.unwrap_or(false);
+        args.show_snoozed |= self.show_snoozed.unwrap_or(false);
+        args.exe_relative |= self.exe_relative.unwrap_or(false);
+        args.compact |= self.compact.unwrap_or(false);
+        args.batch |= self.batch.unwrap_or(false);
+        args.delta |= self.delta.unwrap_or(false);
+        args.strict_delete |= self.strict_delete.unwrap_or(false);
+        args.resolve_on_complete |= self.resolve_on_complete.unwrap_or(false);
+        args.strict_dod |= self.strict_dod.unwrap_or(false);
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+db_path = "boards/team.json"
+page_size = 30
+done_statuses = ["Closed"]
+emoji = true
+confirm_default = false
+"#;
+
+    fn parse_args(args: &[&str]) -> Args {
+        Args::parse(args.iter().map(|arg| arg.to_string())).unwrap()
+    }
+
+    #[test]
+    fn parse_should_read_all_fields() {
+        let config = Config::parse(SAMPLE).unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                db_path: Some("boards/team.json".to_owned()),
+                page_size: Some(30),
+                stale_days: None,
+                progress_width: None,
+                done_statuses: Some(vec![Status::Closed]),
+                emoji: Some(true),
+                history: None,
+                show_snoozed: None,
+                exe_relative: None,
+                compact: None,
+                batch: None,
+                delta: None,
+                strict_delete: None,
+                resolve_on_complete: None,
+                strict_dod: None,
+                confirm_default: Some(false),
+                keys: None,
+            }
+        );
+    }
+
+    #[test]
+    fn from_wizard_should_build_config_from_answers() {
+        let mut answers = "boards/\nyes\n40\n".as_bytes();
+
+        let config = Config::from_wizard(&mut answers);
+
+        assert_eq!(
+            config,
+            Config {
+                db_path: Some("boards/db.json".to_owned()),
+                page_size: Some(40),
+                emoji: Some(true),
+                ..Config::default()
+            }
+        );
+    }
+
+    #[test]
+    fn from_wizard_should_use_defaults_for_empty_answers() {
+        let mut answers = "\n\nlots\n".as_bytes();
+
+        let config = Config::from_wizard(&mut answers);
+
+        assert_eq!(config.db_path, Some("./data/db.json".to_owned()));
+        assert_eq!(config.page_size, None);
+        assert_eq!(config.emoji, Some(false));
+    }
+
+    #[test]
+    fn save_should_write_a_loadable_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        let config = Config::from_wizard(&mut "boards\nn\n\n".as_bytes());
+
+        config.save(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(Config::parse(&content).unwrap(), config);
+    }
+
+    #[test]
+    fn parse_should_reject_unknown_fields() {
+        assert!(Config::parse("colour = true").is_err());
+    }
+
+    #[test]
+    fn parse_should_read_keymap() {
+        let config = Config::parse("[keys]\nback = \"p\"\n").unwrap();
+
+        assert_eq!(
+            config.keys,
+            Some(Keymap {
+                back: "p".to_owned(),
+                ..Keymap::default()
+            })
+        );
+        assert!(Config::parse("[keys]\nback = \"c\"\n").is_err());
+    }
+
+    #[test]
+    fn apply_should_prefer_flags_over_file() {
+        let config = Config::parse(SAMPLE).unwrap();
+
+        let args = config.apply(parse_args(&[
+            "--db",
+            "other.json",
+            "--done",
+            "resolved",
+            "--confirm-default",
+            "yes",
+        ]));
+
+        assert_eq!(args.db_path, Some("other.json".to_owned()));
+        assert_eq!(args.done_statuses, Some(vec![Status::Resolved]));
+        assert_eq!(args.confirm_default, Some(true));
+        assert!(args.emoji);
+    }
+
+    #[test]
+    fn apply_should_fall_back_to_file() {
+        let config = Config::parse(SAMPLE).unwrap();
+
+        let args = config.apply(parse_args(&[]));
+
+        assert_eq!(args.db_path, Some("boards/team.json".to_owned()));
+        assert_eq!(args.done_statuses, Some(vec![Status::Closed]));
+        assert_eq!(args.confirm_default, Some(false));
+        assert!(args.emoji);
+        assert!(!args.compact);
+    }
+}
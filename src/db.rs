@@ -1,203 +1,3644 @@
-use std::{fs::File, io::BufReader};
-
-use anyhow::{anyhow, Result};
-
-use crate::models::{DBState, Epic, Status, Story};
+use std::{
+    collections::HashSet,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, ErrorKind, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
+};
+
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{JiraError, Result};
+use crate::models::{
+    parse_item_ref, slugify, BoardStats, CsvImport, DBState, Epic, ItemEdit, ItemRef,
+    ResolveResult, Status, Story, TextFields,
+};
+
+pub type EpicHook = Box<dyn Fn(&Epic) + Send + Sync>;
 
 pub struct JiraDatabase {
     pub database: Box<dyn Database>,
+    pub done_statuses: Vec<Status>,
+    pub on_epic_closed: Option<EpicHook>,
+    pub strict_delete: bool,
+    pub strict_dod: bool,
+    pub resolve_on_complete: bool,
+    log_path: Option<String>,
+    log_depth: AtomicUsize,
 }
 
 impl JiraDatabase {
     pub fn new(file_path: impl AsRef<str>) -> Self {
+        Self::with_json_file(file_path, false)
+    }
+
+    pub fn with_json_file(file_path: impl AsRef<str>, compact: bool) -> Self {
+        Self::with_database(Box::new(JSONFileDatabase {
+            file_path: file_path.as_ref().to_owned(),
+            compact,
+        }))
+    }
+
+    pub fn init_json_file(file_path: impl AsRef<str>, compact: bool) -> Result<Self> {
+        let database = JSONFileDatabase {
+            file_path: file_path.as_ref().to_owned(),
+            compact,
+        };
+        database.create_if_missing()?;
+
+        Ok(Self::with_database(Box::new(database)))
+    }
+
+    pub fn init_delta_file(file_path: impl AsRef<str>, compact: bool) -> Result<Self> {
+        let snapshot = JSONFileDatabase {
+            file_path: file_path.as_ref().to_owned(),
+            compact,
+        };
+        snapshot.create_if_missing()?;
+
+        Ok(Self::with_database(Box::new(DeltaDatabase::new(snapshot))))
+    }
+
+    pub fn open_json_file(file_path: impl AsRef<str>, compact: bool) -> Result<Self> {
+        let database = JSONFileDatabase {
+            file_path: file_path.as_ref().to_owned(),
+            compact,
+        };
+        database.ensure_exists()?;
+
+        Ok(Self::with_database(Box::new(database)))
+    }
+
+    pub fn open_delta_file(file_path: impl AsRef<str>, compact: bool) -> Result<Self> {
+        let snapshot = JSONFileDatabase {
+            file_path: file_path.as_ref().to_owned(),
+            compact,
+        };
+        snapshot.ensure_exists()?;
+
+        Ok(Self::with_database(Box::new(DeltaDatabase::new(snapshot))))
+    }
+
+    pub fn with_database(database: Box<dyn Database>) -> Self {
         Self {
-            database: Box::new(JSONFileDatabase { 
-                file_path: file_path.as_ref().to_owned(),
-            }),
+            database,
+            done_statuses: vec![Status::Closed, Status::Resolved],
+            on_epic_closed: None,
+            strict_delete: false,
+            strict_dod: false,
+            resolve_on_complete: false,
+            log_path: None,
+            log_depth: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn with_done_statuses(mut self, done_statuses: Vec<Status>) -> Self {
+        self.done_statuses = done_statuses;
+        self
+    }
+
+    pub fn with_epic_closed_hook(mut self, hook: impl Fn(&Epic) + Send + Sync + 'static) -> Self {
+        self.on_epic_closed = Some(Box::new(hook));
+        self
+    }
+
+    pub fn with_strict_delete(mut self, strict_delete: bool) -> Self {
+        self.strict_delete = strict_delete;
+        self
+    }
+
+    pub fn with_strict_dod(mut self, strict_dod: bool) -> Self {
+        self.strict_dod = strict_dod;
+        self
+    }
+
+    pub fn with_resolve_on_complete(mut self, resolve_on_complete: bool) -> Self {
+        self.resolve_on_complete = resolve_on_complete;
+        self
+    }
+
+    pub fn logged(mut self, log_path: &str) -> Self {
+        self.log_path = Some(log_path.to_owned());
+        self
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.database = Box::new(ReadOnlyDatabase {
+            inner: self.database,
+        });
+        self
+    }
+
+    pub fn batched(mut self) -> Self {
+        self.database = Box::new(BatchedDatabase::new(self.database));
+        self
+    }
+
+    pub fn flush(&self) -> Result<()> {
+        self.database
+            .flush()
+            .map_err(|error| storage_error(error, self.database.location()))
+    }
+
+    fn record<R: AffectedIds>(
+        &self,
+        operation: &str,
+        ids: &[u32],
+        run: impl FnOnce() -> Result<R>,
+    ) -> Result<R> {
+        self.record_with(operation, run, |result| {
+            let created = result.iter().flat_map(AffectedIds::affected_ids);
+            ids.iter().copied().chain(created).collect()
+        })
+    }
+
+    fn record_with<R>(
+        &self,
+        operation: &str,
+        run: impl FnOnce() -> Result<R>,
+        ids: impl FnOnce(&Result<R>) -> Vec<u32>,
+    ) -> Result<R> {
+        let depth = self.log_depth.fetch_add(1, Ordering::SeqCst);
+        let result = run();
+        self.log_depth.fetch_sub(1, Ordering::SeqCst);
+
+        if let (Some(log_path), 0) = (&self.log_path, depth) {
+            let entry = OperationLogEntry {
+                at: Utc::now(),
+                operation: operation.to_owned(),
+                ids: ids(&result).into_iter().sorted().dedup().collect(),
+                ok: result.is_ok(),
+                error: result.as_ref().err().map(|error| error.to_string()),
+            };
+            let _ = entry.append(log_path);
         }
+
+        result
+    }
+
+    fn persist(&self, db_state: &mut DBState) -> Result<()> {
+        let stored = self.database.read_db()?;
+        touch_changed_stories(&stored, db_state, Utc::now());
+
+        self.database
+            .write_db(db_state)
+            .map_err(|error| storage_error(error, self.database.location()))
+    }
+
+    pub fn is_done(&self, status: &Status) -> bool {
+        self.done_statuses.contains(status)
     }
 
     pub fn read_db(&self) -> Result<DBState> {
         self.database.read_db()
     }
 
-    pub fn create_epic(&self, epic: Epic) -> Result<u32> {
-        let mut db = self.database.read_db()?;
-        let next_id = db.last_item_id + 1;
-        db.epics.insert(next_id, epic);
-        db.last_item_id = next_id;
+    pub fn seed_id_start(&self, id_start: u32) -> Result<()> {
+        self.record("seed_id_start", &[], || {
+            let mut db = self.database.read_db()?;
+            if !db.epics.is_empty() || !db.stories.is_empty() {
+                return Ok(());
+            }
 
-        self.database.write_db(&db)?;
-        Ok(next_id)
+            db.last_item_id = db.last_item_id.max(id_start.saturating_sub(1));
+            self.persist(&mut db)
+        })
     }
 
-    pub fn create_story(&self, story: Story, epic_id: u32) -> Result<u32> {
-        let mut db = self.database.read_db()?;
-        let next_id = db.last_item_id + 1;
-        db.stories.insert(next_id, story);
-        if let Some(epic) = db.epics.get_mut(&epic_id) {
-            epic.stories.push(next_id);
-            db.last_item_id = next_id;
-            self.database.write_db(&db)?;
-            return Ok(next_id);
+    pub fn reset(&self, keep_settings: bool, id_start: Option<u32>) -> Result<()> {
+        self.record("reset", &[], || {
+            self.transaction(|db| {
+                let mut reset = DBState {
+                    last_item_id: id_start.unwrap_or(1).saturating_sub(1),
+                    ..DBState::default()
+                };
+                if keep_settings {
+                    reset.project_name = db.project_name.take();
+                    reset.project_version = db.project_version.take();
+                }
+
+                *db = reset;
+                Ok(())
+            })
+        })
+    }
+
+    pub fn set_project(&self, name: Option<String>, version: Option<String>) -> Result<()> {
+        self.record("set_project", &[], || {
+            self.transaction(|db| {
+                db.project_name = name;
+                db.project_version = version;
+                Ok(())
+            })
+        })
+    }
+
+    pub fn repair_item_id(&self) -> Result<bool> {
+        self.record("repair_item_id", &[], || {
+            self.transaction(|db| {
+                let max_id = db
+                    .epics
+                    .keys()
+                    .chain(db.stories.keys())
+                    .copied()
+                    .max()
+                    .unwrap_or(0);
+                if db.last_item_id >= max_id {
+                    return Ok(false);
+                }
+
+                db.last_item_id = max_id;
+                Ok(true)
+            })
+        })
+    }
+
+    pub fn transaction<R>(&self, f: impl FnOnce(&mut DBState) -> Result<R>) -> Result<R> {
+        self.record_with(
+            "transaction",
+            || {
+                let mut db = self.database.read_db()?;
+                let result = f(&mut db)?;
+                self.persist(&mut db)?;
+                Ok(result)
+            },
+            |_| vec![],
+        )
+    }
+
+    pub fn stats(&self) -> Result<BoardStats> {
+        let db = self.database.read_db()?;
+        let open_story_count = db
+            .stories
+            .values()
+            .filter(|story| !self.is_done(&story.status))
+            .count();
+
+        Ok(BoardStats {
+            epic_count: db.epics.len(),
+            story_count: db.stories.len(),
+            open_story_count,
+        })
+    }
+
+    pub fn epic_progress(&self, epic_id: u32) -> Result<(usize, usize)> {
+        let db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get(&epic_id)
+            .ok_or(JiraError::EpicNotFound(epic_id))?;
+
+        let done = epic
+            .stories
+            .iter()
+            .filter_map(|story_id| db.stories.get(story_id))
+            .filter(|story| self.is_done(&story.status))
+            .count();
+
+        Ok((done, epic.stories.len()))
+    }
+
+    pub fn epic_percent_complete(&self, epic_id: u32) -> Result<u8> {
+        let db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get(&epic_id)
+            .ok_or(JiraError::EpicNotFound(epic_id))?;
+        if epic.stories.is_empty() {
+            return Ok(0);
         }
 
-        Err(anyhow!("Failed to get epic to insert new story"))
+        let complete: usize = epic
+            .stories
+            .iter()
+            .filter_map(|story_id| db.stories.get(story_id))
+            .map(|story| match self.is_done(&story.status) {
+                true => 100,
+                false => usize::from(story.percent_complete),
+            })
+            .sum();
+
+        Ok(((complete + epic.stories.len() / 2) / epic.stories.len()) as u8)
     }
 
-    pub fn delete_epic(&self, epic_id: u32) -> Result<()> {
-        let mut db = self.database.read_db()?;
-        let stories = match db.epics.get(&epic_id) {
-            Some(epic) => &epic.stories,
-            None => return Err(anyhow!("No epic found with id {}", epic_id)),
-        };
+    pub fn next_actionable_story(&self, epic_id: u32) -> Result<Option<u32>> {
+        let db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get(&epic_id)
+            .ok_or(JiraError::EpicNotFound(epic_id))?;
+
+        Ok(epic.stories.iter().copied().find(|story_id| {
+            db.stories
+                .get(story_id)
+                .is_some_and(|story| story.status == Status::Open)
+        }))
+    }
 
-        for story_id in stories {
-            db.stories.remove(story_id);
+    pub fn export_epic_as_issue(&self, epic_id: u32) -> Result<String> {
+        let db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get(&epic_id)
+            .ok_or(JiraError::EpicNotFound(epic_id))?;
+
+        let mut lines = vec![];
+        if !epic.description.is_empty() {
+            lines.push(epic.description.clone());
+            lines.push(String::new());
+        }
+        for (story_id, story) in epic
+            .stories
+            .iter()
+            .filter_map(|story_id| db.stories.get(story_id).map(|story| (story_id, story)))
+        {
+            let mark = if self.is_done(&story.status) {
+                "x"
+            } else {
+                " "
+            };
+            lines.push(format!(
+                "- [{}] {} {}",
+                mark,
+                ItemRef::Story(*story_id),
+                story.name
+            ));
         }
 
-        if db.epics.remove(&epic_id).is_none() {
-            return Err(anyhow!("Failed to delete epic with id {}", epic_id));
+        Ok(lines.join("\n") + "\n")
+    }
+
+    pub fn export_timeline(&self) -> Result<String> {
+        let db = self.database.read_db()?;
+        Ok(crate::export::mermaid_timeline(&db))
+    }
+
+    pub fn completion_ratio(&self) -> Result<f64> {
+        let db = self.database.read_db()?;
+        if db.stories.is_empty() {
+            return Ok(0.0);
         }
 
-        self.database.write_db(&db)?;
+        let done = db
+            .stories
+            .values()
+            .filter(|story| self.is_done(&story.status))
+            .count();
 
-        Ok(())
+        Ok(done as f64 / db.stories.len() as f64)
     }
 
-    pub fn delete_story(&self, epic_id: u32, story_id: u32) -> Result<()> {
-        let mut db = self.database.read_db()?;
+    pub fn cycle_time(&self, story_id: u32) -> Result<Option<Duration>> {
+        let db = self.database.read_db()?;
+        let story = db
+            .stories
+            .get(&story_id)
+            .ok_or(JiraError::StoryNotFound(story_id))?;
+
+        let started = story
+            .status_history
+            .iter()
+            .find(|(status, _)| *status == Status::InProgress)
+            .map(|(_, at)| *at);
+        let finished = story
+            .status_history
+            .iter()
+            .rev()
+            .take_while(|(status, _)| self.is_done(status))
+            .last()
+            .map(|(_, at)| *at);
+
+        Ok(match (started, finished) {
+            (Some(started), Some(finished)) if finished >= started => Some(finished - started),
+            _ => None,
+        })
+    }
 
-        if db.stories.remove(&story_id).is_none() {
-            return Err(anyhow!("Failed to delete story with id {}", story_id));
+    pub fn status_counts(&self) -> Result<Vec<(Status, usize)>> {
+        let db = self.database.read_db()?;
+
+        Ok([
+            Status::Open,
+            Status::InProgress,
+            Status::Resolved,
+            Status::Closed,
+        ]
+        .into_iter()
+        .map(|status| {
+            let count = db
+                .stories
+                .values()
+                .filter(|story| story.status == status)
+                .count();
+            (status, count)
+        })
+        .collect())
+    }
+
+    pub fn stories_by_status(&self, status: Status) -> Result<Vec<(u32, u32)>> {
+        let db = self.database.read_db()?;
+        let mut matches = Vec::new();
+
+        for (epic_id, epic) in db.epics.iter().sorted_by_key(|(id, _)| **id) {
+            for story_id in &epic.stories {
+                if let Some(story) = db.stories.get(story_id) {
+                    if story.status == status {
+                        matches.push((*epic_id, *story_id));
+                    }
+                }
+            }
         }
 
-        match db.epics.get_mut(&epic_id) {
-            Some(epic) => {
-                epic.stories.retain(|&x| x != story_id);
+        Ok(matches)
+    }
+
+    pub fn stale_stories(&self, days: u32) -> Result<Vec<u32>> {
+        let cutoff = Utc::now() - Duration::days(days.into());
+        let db = self.database.read_db()?;
+
+        Ok(db
+            .stories
+            .iter()
+            .filter(|(_, story)| !self.is_done(&story.status))
+            .filter(|(_, story)| {
+                story
+                    .last_updated()
+                    .is_some_and(|updated_at| updated_at < cutoff)
+            })
+            .map(|(story_id, _)| *story_id)
+            .sorted()
+            .collect())
+    }
+
+    pub fn ready_queue(&self) -> Result<Vec<(u32, u32)>> {
+        let db = self.database.read_db()?;
+        let today = Local::now().date_naive();
+
+        Ok(db
+            .epics
+            .iter()
+            .flat_map(|(epic_id, epic)| {
+                epic.stories
+                    .iter()
+                    .map(move |story_id| (*epic_id, *story_id))
+            })
+            .filter(|(_, story_id)| {
+                db.stories
+                    .get(story_id)
+                    .is_some_and(|story| story.status == Status::Open && !story.is_snoozed(today))
+            })
+            .sorted_by_key(|(_, story_id)| *story_id)
+            .collect())
+    }
+
+    pub fn standup_report(&self) -> Result<String> {
+        let db = self.database.read_db()?;
+        let today = Local::now().date_naive();
+        let yesterday = today - Duration::days(1);
+
+        let mut people: Vec<(String, Vec<String>)> = vec![];
+        for (story_id, story) in db.stories.iter().sorted_by_key(|(id, _)| **id) {
+            let updated_yesterday = story
+                .status_history
+                .last()
+                .is_some_and(|(_, at)| at.with_timezone(&Local).date_naive() == yesterday);
+            if story.status != Status::InProgress && !updated_yesterday {
+                continue;
             }
-            None => {
-                return Err(anyhow!(
-                    "Failed to delete story with id {}. Epic with id {} not found",
-                    story_id,
-                    epic_id
-                ))
+
+            let line = format!(
+                "- {} {} ({}{})",
+                ItemRef::Story(*story_id),
+                story.name,
+                story.status,
+                if updated_yesterday {
+                    ", updated yesterday"
+                } else {
+                    ""
+                }
+            );
+            for assignee in &story.assignees {
+                match people
+                    .iter_mut()
+                    .find(|(name, _)| name.to_lowercase() == assignee.to_lowercase())
+                {
+                    Some((_, lines)) => lines.push(line.clone()),
+                    None => people.push((assignee.clone(), vec![line.clone()])),
+                }
             }
         }
 
-        self.database.write_db(&db)?;
+        let mut lines = vec![format!("# Standup {}", today), String::new()];
+        if people.is_empty() {
+            lines.push("Nothing in progress or updated yesterday.".to_owned());
+        }
+        for (name, items) in people
+            .into_iter()
+            .sorted_by_key(|(name, _)| name.to_lowercase())
+        {
+            lines.push(format!("## {}", name));
+            lines.push(String::new());
+            lines.extend(items);
+            lines.push(String::new());
+        }
 
-        Ok(())
+        Ok(lines.join("\n").trim_end().to_owned() + "\n")
     }
 
-    pub fn update_epic_status(&self, epic_id: u32, status: Status) -> Result<()> {
-        let mut db = self.database.read_db()?;
-        match db.epics.get_mut(&epic_id) {
-            Some(epic) => {
-                epic.status = status;
-                self.database.write_db(&db)?;
-                Ok(())
+    pub fn find_by_slug(&self, slug: &str) -> Result<Option<ItemRef>> {
+        let db = self.database.read_db()?;
+        let slug = slugify(slug);
+
+        Ok(item_slugs(&db)
+            .filter(|(_, other)| *other == slug)
+            .map(|(item, _)| item)
+            .min_by_key(|item| (matches!(item, ItemRef::Story(_)), item.id())))
+    }
+
+    pub fn resolve_reference(&self, input: &str) -> Result<ResolveResult> {
+        let db = self.database.read_db()?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(ResolveResult::None);
+        }
+
+        match parse_item_ref(input) {
+            Some(ItemRef::Epic(id)) if db.epics.contains_key(&id) => {
+                return Ok(ResolveResult::Unique(ItemRef::Epic(id)))
             }
-            None => Err(anyhow!(
-                "Failed to update status of epic with id {}. No epic found",
-                epic_id
-            )),
+            Some(ItemRef::Story(id)) if db.stories.contains_key(&id) => {
+                return Ok(ResolveResult::Unique(ItemRef::Story(id)))
+            }
+            Some(ItemRef::Any(id)) if db.epics.contains_key(&id) => {
+                return Ok(ResolveResult::Unique(ItemRef::Epic(id)))
+            }
+            Some(ItemRef::Any(id)) if db.stories.contains_key(&id) => {
+                return Ok(ResolveResult::Unique(ItemRef::Story(id)))
+            }
+            _ => {}
         }
+        if let Some(item) = self.find_by_slug(input)? {
+            return Ok(ResolveResult::Unique(item));
+        }
+
+        let needle = input.to_lowercase();
+        let matches = |id: &u32, name: &str| {
+            id.to_string().starts_with(&needle) || name.to_lowercase().contains(&needle)
+        };
+        let candidates = db
+            .epics
+            .iter()
+            .filter(|(id, epic)| matches(id, &epic.name))
+            .map(|(id, _)| ItemRef::Epic(*id))
+            .chain(
+                db.stories
+                    .iter()
+                    .filter(|(id, story)| matches(id, &story.name))
+                    .map(|(id, _)| ItemRef::Story(*id)),
+            )
+            .sorted_by_key(ItemRef::id)
+            .collect_vec();
+
+        Ok(match candidates.len() {
+            0 => ResolveResult::None,
+            1 => ResolveResult::Unique(candidates[0]),
+            _ => ResolveResult::Ambiguous(candidates),
+        })
     }
 
-    pub fn update_story_status(&self, story_id: u32, status: Status) -> Result<()> {
+    pub fn create_epic(&self, epic: Epic) -> Result<u32> {
+        self.create_epic_returning(epic).map(|(id, _)| id)
+    }
+
+    pub fn create_epic_returning(&self, epic: Epic) -> Result<(u32, Epic)> {
+        self.record("create_epic", &[], || {
+            let mut db = self.database.read_db()?;
+            let next_id = next_item_id(&db)?;
+            let mut epic = epic;
+            epic.slug = unique_slug(&db, &epic.name, ItemRef::Epic(next_id));
+            db.epics.insert(next_id, epic.clone());
+            db.last_item_id = next_id;
+
+            self.persist(&mut db)?;
+            Ok((next_id, epic))
+        })
+    }
+
+    pub fn ensure_epic(&self, name: &str, description: &str) -> Result<u32> {
+        self.record("ensure_epic", &[], || {
+            self.transaction(|db| {
+                let existing = db
+                    .epics
+                    .iter()
+                    .filter(|(_, epic)| epic.name.to_lowercase() == name.to_lowercase())
+                    .map(|(epic_id, _)| *epic_id)
+                    .min();
+                if let Some(epic_id) = existing {
+                    return Ok(epic_id);
+                }
+
+                let next_id = next_item_id(db)?;
+                let mut epic = Epic::new(name.to_owned(), description.to_owned());
+                epic.slug = unique_slug(db, name, ItemRef::Epic(next_id));
+                db.epics.insert(next_id, epic);
+                db.last_item_id = next_id;
+                Ok(next_id)
+            })
+        })
+    }
+
+    pub fn create_story(&self, story: Story, epic_id: u32) -> Result<u32> {
+        self.create_story_returning(story, epic_id)
+            .map(|(id, _)| id)
+    }
+
+    pub fn create_story_returning(&self, story: Story, epic_id: u32) -> Result<(u32, Story)> {
+        self.record("create_story", &[epic_id], || {
+            let mut db = self.database.read_db()?;
+            let next_id = next_item_id(&db)?;
+            let mut story = story;
+            story.slug = unique_slug(&db, &story.name, ItemRef::Story(next_id));
+            db.stories.insert(next_id, story.clone());
+            if let Some(epic) = db.epics.get_mut(&epic_id) {
+                epic.stories.push(next_id);
+                db.last_item_id = next_id;
+                self.persist(&mut db)?;
+                return Ok((next_id, story));
+            }
+
+            Err(JiraError::EpicNotFound(epic_id))
+        })
+    }
+
+    pub fn import_stories_csv(&self, epic_id: u32, path: &str) -> Result<CsvImport> {
+        self.record("import_stories_csv", &[epic_id], || {
+            let records = parse_csv(&std::fs::read_to_string(path)?)?;
+
+            self.transaction(|db| {
+                if !db.epics.contains_key(&epic_id) {
+                    return Err(JiraError::EpicNotFound(epic_id));
+                }
+
+                let mut import = CsvImport::default();
+                for (index, (line, fields)) in records.into_iter().enumerate() {
+                    if index == 0 && fields[0].trim().eq_ignore_ascii_case("name") {
+                        continue;
+                    }
+                    let malformed = |reason: &str| JiraError::MalformedCsv {
+                        line,
+                        reason: reason.to_owned(),
+                    };
+                    if fields.len() > 3 {
+                        return Err(malformed("expected name, description and status columns"));
+                    }
+                    let field = |index: usize| fields.get(index).map_or("", |field| field.trim());
+                    if field(0).is_empty() {
+                        return Err(malformed("story name is empty"));
+                    }
+
+                    let mut story = Story::new(field(0).to_owned(), field(1).to_owned());
+                    if !field(2).is_empty() {
+                        match field(2).parse::<Status>() {
+                            Ok(status) => story.set_status(status, Utc::now()),
+                            Err(error) => import
+                                .warnings
+                                .push((line, format!("{}, importing as Open", error))),
+                        }
+                    }
+
+                    let story_id = next_item_id(db)?;
+                    story.slug = unique_slug(db, &story.name, ItemRef::Story(story_id));
+                    db.stories.insert(story_id, story);
+                    db.epics
+                        .entry(epic_id)
+                        .and_modify(|epic| epic.stories.push(story_id));
+                    db.last_item_id = story_id;
+                    import.story_ids.push(story_id);
+                }
+
+                Ok(import)
+            })
+        })
+    }
+
+    pub fn delete_epic(&self, epic_id: u32) -> Result<()> {
+        self.record("delete_epic", &[epic_id], || {
+            let mut db = self.database.read_db()?;
+            let epic = match db.epics.get(&epic_id) {
+                Some(epic) => epic,
+                None => return Err(JiraError::EpicNotFound(epic_id)),
+            };
+            let stories = &epic.stories;
+
+            self.ensure_deletable(ItemRef::Epic(epic_id), &epic.status)?;
+            for story_id in stories {
+                if let Some(story) = db.stories.get(story_id) {
+                    self.ensure_deletable(ItemRef::Story(*story_id), &story.status)?;
+                }
+            }
+
+            for story_id in stories {
+                db.stories.remove(story_id);
+            }
+
+            if db.epics.remove(&epic_id).is_none() {
+                return Err(JiraError::EpicNotFound(epic_id));
+            }
+
+            self.persist(&mut db)?;
+
+            Ok(())
+        })
+    }
+
+    pub fn delete_epic_keep_stories(&self, epic_id: u32, reassign_to: Option<u32>) -> Result<()> {
+        let ids = [Some(epic_id), reassign_to]
+            .into_iter()
+            .flatten()
+            .collect_vec();
+        self.record("delete_epic_keep_stories", &ids, || {
+            self.remove_epic_keeping_stories(epic_id, reassign_to, true)
+        })
+    }
+
+    fn remove_epic_keeping_stories(
+        &self,
+        epic_id: u32,
+        reassign_to: Option<u32>,
+        strict: bool,
+    ) -> Result<()> {
         let mut db = self.database.read_db()?;
-        match db.stories.get_mut(&story_id) {
-            Some(story) => {
-                story.status = status;
-                self.database.write_db(&db)?;
-                Ok(())
+
+        if reassign_to == Some(epic_id) {
+            return Err(JiraError::InvalidOperation(format!(
+                "Cannot reassign stories of epic with id {} to itself",
+                epic_id
+            )));
+        }
+
+        let epic = db
+            .epics
+            .get(&epic_id)
+            .ok_or(JiraError::EpicNotFound(epic_id))?;
+        if strict {
+            self.ensure_deletable(ItemRef::Epic(epic_id), &epic.status)?;
+        }
+        let stories = epic.stories.clone();
+        db.epics.remove(&epic_id);
+
+        if let Some(target_id) = reassign_to {
+            match db.epics.get_mut(&target_id) {
+                Some(target) => target.stories.extend(stories),
+                None => return Err(JiraError::EpicNotFound(target_id)),
             }
-            None => Err(anyhow!(
-                "Failed to update status of story with id {}. No story found",
-                story_id
-            )),
         }
+
+        self.persist(&mut db)
     }
-}
 
-pub trait Database {
-    fn read_db(&self) -> Result<DBState>;
-    fn write_db(&self, db_state: &DBState) -> Result<()>;
-}
+    pub fn merge_epics(&self, source: u32, dest: u32) -> Result<()> {
+        self.record("merge_epics", &[source, dest], || {
+            if source == dest {
+                return Err(JiraError::InvalidOperation(format!(
+                    "Cannot merge epic with id {} into itself",
+                    source
+                )));
+            }
 
-struct JSONFileDatabase {
-    pub file_path: String,
-}
+            self.remove_epic_keeping_stories(source, Some(dest), false)
+        })
+    }
 
-impl Database for JSONFileDatabase {
-    fn read_db(&self) -> Result<DBState> {
-        let file = File::open(&self.file_path)?;
-        let reader = BufReader::new(file);
-        let database: DBState = serde_json::from_reader(reader)?;
+    pub fn delete_story(&self, epic_id: u32, story_id: u32) -> Result<()> {
+        self.record("delete_story", &[epic_id, story_id], || {
+            let mut db = self.database.read_db()?;
+
+            let epic = db
+                .epics
+                .get_mut(&epic_id)
+                .ok_or(JiraError::EpicNotFound(epic_id))?;
+            let story = db
+                .stories
+                .get(&story_id)
+                .ok_or(JiraError::StoryNotFound(story_id))?;
+            if !epic.stories.contains(&story_id) {
+                return Err(JiraError::InvalidOperation(format!(
+                    "{} does not belong to {}",
+                    ItemRef::Story(story_id),
+                    ItemRef::Epic(epic_id)
+                )));
+            }
+            self.ensure_deletable(ItemRef::Story(story_id), &story.status)?;
 
-        Ok(database)
+            epic.stories.retain(|&x| x != story_id);
+            db.stories.remove(&story_id);
+            self.persist(&mut db)?;
+
+            Ok(())
+        })
     }
 
-    fn write_db(&self, db_state: &DBState) -> Result<()> {
-        let content = serde_json::to_string_pretty(db_state)?;
-        std::fs::write(&self.file_path, content)?;
+    fn ensure_deletable(&self, item: ItemRef, status: &Status) -> Result<()> {
+        if self.strict_delete && *status != Status::Closed {
+            return Err(JiraError::InvalidOperation(format!(
+                "{} is {}; close it before deleting",
+                item, status
+            )));
+        }
 
         Ok(())
     }
-}
 
-pub mod test_utils {
-    use std::{cell::RefCell, collections::HashMap};
+    pub fn update_epic_status(&self, epic_id: u32, status: Status) -> Result<()> {
+        self.record("update_epic_status", &[epic_id], || {
+            self.set_epic_status(epic_id, status, true)
+        })
+    }
 
-    use super::*;
+    pub fn closed_stories_reopened_by(
+        &self,
+        epic_id: u32,
+        status: &Status,
+    ) -> Result<Option<usize>> {
+        let db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get(&epic_id)
+            .ok_or(JiraError::EpicNotFound(epic_id))?;
+        if epic.status != Status::Closed || !matches!(status, Status::Open | Status::InProgress) {
+            return Ok(None);
+        }
 
-    pub struct MockDB {
-        last_written_state: RefCell<DBState>,
+        Ok(Some(
+            epic.stories
+                .iter()
+                .filter_map(|story_id| db.stories.get(story_id))
+                .filter(|story| story.status == Status::Closed)
+                .count(),
+        ))
     }
 
-    impl MockDB {
-        pub fn new() -> Self {
-            Self {
-                last_written_state: RefCell::new(DBState {
-                    last_item_id: 0,
-                    epics: HashMap::new(),
-                    stories: HashMap::new(),
-                }),
+    pub fn set_epic_status(&self, epic_id: u32, status: Status, force: bool) -> Result<()> {
+        self.record("set_epic_status", &[epic_id], || {
+            if !force {
+                if let Some(closed) = self.closed_stories_reopened_by(epic_id, &status)? {
+                    return Err(JiraError::InvalidOperation(format!(
+                        "{} is closed and has {} closed stories; reopening it needs force",
+                        ItemRef::Epic(epic_id),
+                        closed
+                    )));
+                }
             }
-        }
+
+            let mut db = self.database.read_db()?;
+            match db.epics.get_mut(&epic_id) {
+                Some(epic) => {
+                    self.check_dod(epic_id, epic, &status)?;
+                    let previous = std::mem::replace(&mut epic.status, status);
+                    let epic = epic.clone();
+                    self.persist(&mut db)?;
+
+                    self.notify_epic_closed(&previous, &epic);
+                    Ok(())
+                }
+                None => Err(JiraError::EpicNotFound(epic_id)),
+            }
+        })
     }
 
-    impl Default for MockDB {
-        fn default() -> Self {
-            Self::new()
-        }
+    pub fn edit_epic(&self, epic_id: u32, edit: ItemEdit) -> Result<()> {
+        self.record("edit_epic", &[epic_id], || {
+            let (previous, epic) = self.transaction(|db| {
+                let epic = db
+                    .epics
+                    .get_mut(&epic_id)
+                    .ok_or(JiraError::EpicNotFound(epic_id))?;
+                let previous = epic.status.clone();
+                let name = epic.name.clone();
+                let mut status = epic.status.clone();
+                edit.apply(&mut epic.name, &mut epic.description, &mut status);
+                self.check_dod(epic_id, epic, &status)?;
+                epic.status = status;
+                if epic.name != name {
+                    let name = epic.name.clone();
+                    let slug = unique_slug(db, &name, ItemRef::Epic(epic_id));
+                    db.epics.entry(epic_id).and_modify(|epic| epic.slug = slug);
+                }
+
+                Ok((previous, db.epics[&epic_id].clone()))
+            })?;
+
+            self.notify_epic_closed(&previous, &epic);
+            Ok(())
+        })
     }
 
-    impl Database for MockDB {
-        fn read_db(&self) -> Result<DBState> {
-            let state = self.last_written_state.borrow().clone();
-            Ok(state)
-        }
+    pub fn edit_story(&self, story_id: u32, edit: ItemEdit) -> Result<()> {
+        self.record("edit_story", &[story_id], || {
+            self.transaction(|db| {
+                let story = db
+                    .stories
+                    .get_mut(&story_id)
+                    .ok_or(JiraError::StoryNotFound(story_id))?;
+                let name = story.name.clone();
+                let mut status = story.status.clone();
+                edit.apply(&mut story.name, &mut story.description, &mut status);
+                story.set_status(status, Utc::now());
+                if story.name != name {
+                    let name = story.name.clone();
+                    let slug = unique_slug(db, &name, ItemRef::Story(story_id));
+                    db.stories
+                        .entry(story_id)
+                        .and_modify(|story| story.slug = slug);
+                }
 
-        fn write_db(&self, db_state: &DBState) -> Result<()> {
-            let latest_state = &self.last_written_state;
-            *latest_state.borrow_mut() = db_state.clone();
+                Ok(())
+            })
+        })
+    }
+
+    pub fn replace_text(
+        &self,
+        find: &str,
+        replace: &str,
+        fields: TextFields,
+        case_sensitive: bool,
+    ) -> Result<usize> {
+        self.record("replace_text", &[], || {
+            if find.is_empty() {
+                return Err(JiraError::InvalidOperation(
+                    "the text to find cannot be empty".to_owned(),
+                ));
+            }
+
+            self.transaction(|db| {
+                let replace_in = |text: &mut String, enabled: bool| {
+                    if !enabled {
+                        return false;
+                    }
+                    let replaced = replace_text_in(text, find, replace, case_sensitive);
+                    let changed = replaced != *text;
+                    *text = replaced;
+                    changed
+                };
+
+                let mut renamed = vec![];
+                let mut changed = 0;
+                for (epic_id, epic) in db.epics.iter_mut() {
+                    let name = replace_in(&mut epic.name, fields.names);
+                    let description = replace_in(&mut epic.description, fields.descriptions);
+                    if name {
+                        renamed.push(ItemRef::Epic(*epic_id));
+                    }
+                    changed += usize::from(name || description);
+                }
+                for (story_id, story) in db.stories.iter_mut() {
+                    let name = replace_in(&mut story.name, fields.names);
+                    let description = replace_in(&mut story.description, fields.descriptions);
+                    if name {
+                        renamed.push(ItemRef::Story(*story_id));
+                    }
+                    changed += usize::from(name || description);
+                }
+
+                for item in renamed.into_iter().sorted_by_key(ItemRef::id) {
+                    match item {
+                        ItemRef::Epic(id) => {
+                            let slug = unique_slug(db, &db.epics[&id].name, item);
+                            db.epics.entry(id).and_modify(|epic| epic.slug = slug);
+                        }
+                        ItemRef::Story(id) | ItemRef::Any(id) => {
+                            let slug = unique_slug(db, &db.stories[&id].name, item);
+                            db.stories.entry(id).and_modify(|story| story.slug = slug);
+                        }
+                    }
+                }
+
+                Ok(changed)
+            })
+        })
+    }
+
+    pub fn set_statuses(&self, updates: Vec<(ItemRef, Status)>) -> Result<()> {
+        let ids = updates.iter().map(|(item, _)| item.id()).collect_vec();
+        self.record("set_statuses", &ids, || {
+            let now = Utc::now();
+            let epic_changes = self.transaction(|db| {
+                let updates = updates
+                    .into_iter()
+                    .map(|(item, status)| {
+                        let item = match item {
+                            ItemRef::Epic(id) if db.epics.contains_key(&id) => item,
+                            ItemRef::Story(id) if db.stories.contains_key(&id) => item,
+                            ItemRef::Any(id) if db.epics.contains_key(&id) => ItemRef::Epic(id),
+                            ItemRef::Any(id) if db.stories.contains_key(&id) => ItemRef::Story(id),
+                            ItemRef::Epic(id) => return Err(JiraError::EpicNotFound(id)),
+                            ItemRef::Story(id) | ItemRef::Any(id) => {
+                                return Err(JiraError::StoryNotFound(id))
+                            }
+                        };
+                        Ok((item, status))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                let mut epic_changes = vec![];
+                for (item, status) in updates {
+                    match item {
+                        ItemRef::Epic(id) => {
+                            if let Some(epic) = db.epics.get_mut(&id) {
+                                self.check_dod(id, epic, &status)?;
+                                let previous = std::mem::replace(&mut epic.status, status);
+                                epic_changes.push((previous, epic.clone()));
+                            }
+                        }
+                        _ => {
+                            if let Some(story) = db.stories.get_mut(&item.id()) {
+                                story.set_status(status, now);
+                            }
+                        }
+                    }
+                }
+
+                Ok(epic_changes)
+            })?;
+
+            for (previous, epic) in epic_changes {
+                self.notify_epic_closed(&previous, &epic);
+            }
             Ok(())
+        })
+    }
+
+    pub fn bulk_transition(&self, epic_id: u32, from: Status, to: Status) -> Result<usize> {
+        self.record("bulk_transition", &[epic_id], || {
+            let now = Utc::now();
+            self.transaction(|db| {
+                let epic = db
+                    .epics
+                    .get(&epic_id)
+                    .ok_or(JiraError::EpicNotFound(epic_id))?;
+
+                let mut changed = 0;
+                for story_id in &epic.stories {
+                    if let Some(story) = db.stories.get_mut(story_id) {
+                        if story.status == from && from != to {
+                            story.set_status(to.clone(), now);
+                            changed += 1;
+                        }
+                    }
+                }
+
+                Ok(changed)
+            })
+        })
+    }
+
+    pub fn close_epic_cascade(&self, epic_id: u32) -> Result<()> {
+        self.record("close_epic_cascade", &[epic_id], || {
+            let now = Utc::now();
+            let (previous, epic) = self.transaction(|db| {
+                let epic = db
+                    .epics
+                    .get_mut(&epic_id)
+                    .ok_or(JiraError::EpicNotFound(epic_id))?;
+                self.check_dod(epic_id, epic, &Status::Closed)?;
+                let previous = std::mem::replace(&mut epic.status, Status::Closed);
+                let epic = epic.clone();
+
+                for story_id in &epic.stories {
+                    if let Some(story) = db.stories.get_mut(story_id) {
+                        story.set_status(Status::Closed, now);
+                    }
+                }
+
+                Ok((previous, epic))
+            })?;
+
+            self.notify_epic_closed(&previous, &epic);
+            Ok(())
+        })
+    }
+
+    fn check_dod(&self, epic_id: u32, epic: &Epic, status: &Status) -> Result<()> {
+        let unchecked = epic.unchecked_dod();
+        if !self.strict_dod
+            || *status != Status::Closed
+            || epic.status == Status::Closed
+            || unchecked.is_empty()
+        {
+            return Ok(());
         }
+
+        Err(JiraError::InvalidOperation(format!(
+            "{} cannot be closed until its definition of done is checked: {}",
+            ItemRef::Epic(epic_id),
+            unchecked.join(", ")
+        )))
     }
+
+    pub fn add_dod_item(&self, epic_id: u32, name: String) -> Result<()> {
+        self.record("add_dod_item", &[epic_id], || {
+            let name = name.trim().to_owned();
+            if name.is_empty() {
+                return Err(JiraError::InvalidOperation(
+                    "a definition of done item needs a name".to_owned(),
+                ));
+            }
+
+            self.transaction(|db| {
+                let epic = db
+                    .epics
+                    .get_mut(&epic_id)
+                    .ok_or(JiraError::EpicNotFound(epic_id))?;
+                epic.dod.push((name, false));
+                Ok(())
+            })
+        })
+    }
+
+    pub fn toggle_dod_item(&self, epic_id: u32, index: usize) -> Result<bool> {
+        self.record("toggle_dod_item", &[epic_id], || {
+            self.transaction(|db| {
+                let epic = db
+                    .epics
+                    .get_mut(&epic_id)
+                    .ok_or(JiraError::EpicNotFound(epic_id))?;
+                epic.toggle_dod_item(index).ok_or_else(|| {
+                    JiraError::InvalidOperation(format!(
+                        "{} has no definition of done item {}",
+                        ItemRef::Epic(epic_id),
+                        index + 1
+                    ))
+                })
+            })
+        })
+    }
+
+    fn notify_epic_closed(&self, previous: &Status, epic: &Epic) {
+        if *previous == Status::Closed || epic.status != Status::Closed {
+            return;
+        }
+        if let Some(hook) = &self.on_epic_closed {
+            hook(epic);
+        }
+    }
+
+    pub fn update_story_status(&self, story_id: u32, status: Status) -> Result<()> {
+        self.record("update_story_status", &[story_id], || {
+            let mut db = self.database.read_db()?;
+            match db.stories.get_mut(&story_id) {
+                Some(story) => {
+                    story.set_status(status, Utc::now());
+                    self.persist(&mut db)?;
+                    Ok(())
+                }
+                None => Err(JiraError::StoryNotFound(story_id)),
+            }
+        })
+    }
+
+    pub fn reopen_story(&self, story_id: u32, reason: String) -> Result<()> {
+        self.record("reopen_story", &[story_id], || {
+            self.transaction(|db| {
+                let story = db
+                    .stories
+                    .get_mut(&story_id)
+                    .ok_or(JiraError::StoryNotFound(story_id))?;
+                if !matches!(story.status, Status::Closed | Status::Resolved) {
+                    return Err(JiraError::InvalidOperation(format!(
+                        "{} is {}; only closed or resolved stories can be reopened",
+                        ItemRef::Story(story_id),
+                        story.status
+                    )));
+                }
+
+                let at = Utc::now();
+                story.set_status(Status::Open, at);
+                let reason = reason.trim();
+                if !reason.is_empty() {
+                    story.reopen_reasons.push((at, reason.to_owned()));
+                }
+                Ok(())
+            })
+        })
+    }
+
+    pub fn snooze_story(&self, story_id: u32, until: NaiveDate) -> Result<()> {
+        self.record("snooze_story", &[story_id], || {
+            let today = Local::now().date_naive();
+            if until <= today {
+                return Err(JiraError::InvalidOperation(format!(
+                    "cannot snooze until {}; pick a date after {}",
+                    until, today
+                )));
+            }
+
+            let mut db = self.database.read_db()?;
+            let story = db
+                .stories
+                .get_mut(&story_id)
+                .ok_or(JiraError::StoryNotFound(story_id))?;
+            story.snoozed_until = Some(until);
+
+            self.persist(&mut db)?;
+            Ok(())
+        })
+    }
+
+    pub fn set_due_date(&self, story_id: u32, due_date: Option<NaiveDate>) -> Result<()> {
+        self.record("set_due_date", &[story_id], || {
+            self.transaction(|db| {
+                let story = db
+                    .stories
+                    .get_mut(&story_id)
+                    .ok_or(JiraError::StoryNotFound(story_id))?;
+                story.due_date = due_date;
+                Ok(())
+            })
+        })
+    }
+
+    pub fn extract_subtasks(&self, story_id: u32) -> Result<usize> {
+        self.record("extract_subtasks", &[story_id], || {
+            self.transaction(|db| {
+                let story = db
+                    .stories
+                    .get_mut(&story_id)
+                    .ok_or(JiraError::StoryNotFound(story_id))?;
+
+                Ok(story.extract_subtasks())
+            })
+        })
+    }
+
+    pub fn add_subtask(&self, story_id: u32, name: String) -> Result<()> {
+        self.record("add_subtask", &[story_id], || {
+            let mut db = self.database.read_db()?;
+            match db.stories.get_mut(&story_id) {
+                Some(story) => {
+                    story.add_subtask(name);
+                    self.persist(&mut db)?;
+                    Ok(())
+                }
+                None => Err(JiraError::StoryNotFound(story_id)),
+            }
+        })
+    }
+
+    pub fn toggle_epic_star(&self, epic_id: u32) -> Result<bool> {
+        self.record("toggle_epic_star", &[epic_id], || {
+            let mut db = self.database.read_db()?;
+            let epic = db
+                .epics
+                .get_mut(&epic_id)
+                .ok_or(JiraError::EpicNotFound(epic_id))?;
+            epic.starred = !epic.starred;
+            let starred = epic.starred;
+
+            self.persist(&mut db)?;
+            Ok(starred)
+        })
+    }
+
+    pub fn toggle_story_pin(&self, story_id: u32) -> Result<bool> {
+        self.record("toggle_story_pin", &[story_id], || {
+            let mut db = self.database.read_db()?;
+            let story = db
+                .stories
+                .get_mut(&story_id)
+                .ok_or(JiraError::StoryNotFound(story_id))?;
+            story.pinned = !story.pinned;
+            let pinned = story.pinned;
+
+            self.persist(&mut db)?;
+            Ok(pinned)
+        })
+    }
+
+    pub fn add_assignee(&self, story_id: u32, name: &str) -> Result<bool> {
+        self.record("add_assignee", &[story_id], || {
+            let mut db = self.database.read_db()?;
+            let story = db
+                .stories
+                .get_mut(&story_id)
+                .ok_or(JiraError::StoryNotFound(story_id))?;
+            if !story.add_assignee(name) {
+                return Ok(false);
+            }
+
+            self.persist(&mut db)?;
+            Ok(true)
+        })
+    }
+
+    pub fn remove_assignee(&self, story_id: u32, name: &str) -> Result<bool> {
+        self.record("remove_assignee", &[story_id], || {
+            let mut db = self.database.read_db()?;
+            let story = db
+                .stories
+                .get_mut(&story_id)
+                .ok_or(JiraError::StoryNotFound(story_id))?;
+            if !story.remove_assignee(name) {
+                return Ok(false);
+            }
+
+            self.persist(&mut db)?;
+            Ok(true)
+        })
+    }
+
+    pub fn stories_assigned_to(&self, name: &str) -> Result<Vec<u32>> {
+        let db = self.database.read_db()?;
+        Ok(db
+            .stories
+            .iter()
+            .filter(|(_, story)| story.is_assigned_to(name))
+            .map(|(story_id, _)| *story_id)
+            .sorted()
+            .collect())
+    }
+
+    pub fn set_percent_complete(&self, story_id: u32, percent: u8) -> Result<()> {
+        self.record("set_percent_complete", &[story_id], || {
+            if percent > 100 {
+                return Err(JiraError::InvalidOperation(format!(
+                    "percent complete must be between 0 and 100, got {}",
+                    percent
+                )));
+            }
+
+            self.transaction(|db| {
+                let story = db
+                    .stories
+                    .get_mut(&story_id)
+                    .ok_or(JiraError::StoryNotFound(story_id))?;
+                story.percent_complete = percent;
+                if percent == 100 && self.resolve_on_complete && !self.is_done(&story.status) {
+                    story.set_status(Status::Resolved, Utc::now());
+                }
+                Ok(())
+            })
+        })
+    }
+
+    pub fn track_time(
+        &self,
+        story_id: u32,
+        estimate_hours: Option<f32>,
+        actual_hours: Option<f32>,
+    ) -> Result<()> {
+        self.record("track_time", &[story_id], || {
+            if let Some(hours) = [estimate_hours, actual_hours]
+                .into_iter()
+                .flatten()
+                .find(|hours| !hours.is_finite() || *hours < 0.0)
+            {
+                return Err(JiraError::InvalidOperation(format!(
+                    "hours must be zero or more, got {}",
+                    hours
+                )));
+            }
+
+            self.transaction(|db| {
+                let story = db
+                    .stories
+                    .get_mut(&story_id)
+                    .ok_or(JiraError::StoryNotFound(story_id))?;
+                story.estimate_hours = estimate_hours.or(story.estimate_hours);
+                story.actual_hours = actual_hours.or(story.actual_hours);
+                Ok(())
+            })
+        })
+    }
+
+    pub fn time_rollup(&self, epic_id: u32) -> Result<(f32, f32)> {
+        let db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get(&epic_id)
+            .ok_or(JiraError::EpicNotFound(epic_id))?;
+
+        Ok(epic
+            .stories
+            .iter()
+            .filter_map(|story_id| db.stories.get(story_id))
+            .fold((0.0, 0.0), |(estimate, actual), story| {
+                (
+                    estimate + story.estimate_hours.unwrap_or(0.0),
+                    actual + story.actual_hours.unwrap_or(0.0),
+                )
+            }))
+    }
+
+    pub fn critical_path(&self, epic_id: u32) -> Result<Vec<u32>> {
+        let db = self.database.read_db()?;
+        let epic = db
+            .epics
+            .get(&epic_id)
+            .ok_or(JiraError::EpicNotFound(epic_id))?;
+
+        let estimate = |story_id: &u32| {
+            db.stories
+                .get(story_id)
+                .and_then(|story| story.estimate_hours)
+                .unwrap_or(0.0)
+        };
+        Ok(epic
+            .stories
+            .iter()
+            .filter(|story_id| db.stories.contains_key(story_id))
+            .rev()
+            .max_by(|a, b| estimate(a).total_cmp(&estimate(b)))
+            .map(|story_id| vec![*story_id])
+            .unwrap_or_default())
+    }
+
+    pub fn toggle_subtask(&self, story_id: u32, index: usize) -> Result<()> {
+        self.record("toggle_subtask", &[story_id], || {
+            let mut db = self.database.read_db()?;
+            let story = match db.stories.get_mut(&story_id) {
+                Some(story) => story,
+                None => return Err(JiraError::StoryNotFound(story_id)),
+            };
+
+            if story.toggle_subtask(index).is_none() {
+                return Err(JiraError::SubtaskNotFound {
+                    story_id,
+                    number: index + 1,
+                });
+            }
+
+            self.persist(&mut db)?;
+
+            Ok(())
+        })
+    }
+}
+
+fn item_slugs(db: &DBState) -> impl Iterator<Item = (ItemRef, String)> + '_ {
+    let slug = |slug: &str, name: &str| match slug.is_empty() {
+        true => slugify(name),
+        false => slug.to_owned(),
+    };
+
+    db.epics
+        .iter()
+        .map(move |(id, epic)| (ItemRef::Epic(*id), slug(&epic.slug, &epic.name)))
+        .chain(
+            db.stories
+                .iter()
+                .map(move |(id, story)| (ItemRef::Story(*id), slug(&story.slug, &story.name))),
+        )
+}
+
+fn touch_changed_stories(stored: &DBState, db: &mut DBState, at: DateTime<Utc>) {
+    for (story_id, story) in db.stories.iter_mut() {
+        let Some(before) = stored.stories.get(story_id) else {
+            continue;
+        };
+        story.updated_at = before.updated_at;
+        if story != before {
+            story.updated_at = Some(at);
+        }
+    }
+}
+
+fn unique_slug(db: &DBState, name: &str, item: ItemRef) -> String {
+    let taken = item_slugs(db)
+        .filter(|(other, _)| {
+            *other != item && matches!(other, ItemRef::Epic(_)) == matches!(item, ItemRef::Epic(_))
+        })
+        .map(|(_, slug)| slug)
+        .collect::<HashSet<_>>();
+    let base = slugify(name);
+
+    let mut slug = base.clone();
+    let mut suffix = 2;
+    while taken.contains(&slug) {
+        slug = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    slug
+}
+
+fn replace_text_in(text: &str, find: &str, replace: &str, case_sensitive: bool) -> String {
+    if case_sensitive {
+        return text.replace(find, replace);
+    }
+
+    let match_len = |rest: &str| {
+        let mut chars = rest.chars();
+        find.chars().try_fold(0, |len, f| {
+            let c = chars.next()?;
+            c.to_lowercase()
+                .eq(f.to_lowercase())
+                .then_some(len + c.len_utf8())
+        })
+    };
+
+    let mut replaced = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        match match_len(rest) {
+            Some(len) => {
+                replaced.push_str(replace);
+                rest = &rest[len..];
+            }
+            None => {
+                replaced.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    replaced
+}
+
+fn parse_csv(content: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut records = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => fields.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                fields.push(std::mem::take(&mut field));
+                if fields.iter().any(|field| !field.trim().is_empty()) {
+                    records.push((record_line, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                record_line = line;
+            }
+            (_, c) => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+
+    if quoted {
+        return Err(JiraError::MalformedCsv {
+            line: record_line,
+            reason: "unterminated quoted field".to_owned(),
+        });
+    }
+    fields.push(field);
+    if fields.iter().any(|field| !field.trim().is_empty()) {
+        records.push((record_line, fields));
+    }
+
+    Ok(records)
+}
+
+fn next_item_id(db: &DBState) -> Result<u32> {
+    db.last_item_id
+        .checked_add(1)
+        .ok_or(JiraError::IdsExhausted(db.last_item_id))
+}
+
+fn storage_error(error: JiraError, location: Option<String>) -> JiraError {
+    match error {
+        JiraError::IoError(error) if error.kind() == ErrorKind::PermissionDenied => {
+            let directory = location
+                .and_then(|path| {
+                    Path::new(&path)
+                        .parent()
+                        .map(|parent| parent.display().to_string())
+                })
+                .filter(|directory| !directory.is_empty())
+                .unwrap_or_else(|| ".".to_owned());
+            JiraError::NotWritable(directory)
+        }
+        error => error,
+    }
+}
+
+pub trait Database: Send + Sync {
+    fn read_db(&self) -> Result<DBState>;
+    fn write_db(&self, db_state: &DBState) -> Result<()>;
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn location(&self) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Serialize)]
+struct OperationLogEntry {
+    at: DateTime<Utc>,
+    operation: String,
+    ids: Vec<u32>,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl OperationLogEntry {
+    fn append(&self, log_path: &str) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_path)?;
+        writeln!(file, "{}", serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+trait AffectedIds {
+    fn affected_ids(&self) -> Vec<u32> {
+        vec![]
+    }
+}
+
+impl AffectedIds for () {}
+
+impl AffectedIds for bool {}
+
+impl AffectedIds for usize {}
+
+impl AffectedIds for u32 {
+    fn affected_ids(&self) -> Vec<u32> {
+        vec![*self]
+    }
+}
+
+impl AffectedIds for CsvImport {
+    fn affected_ids(&self) -> Vec<u32> {
+        self.story_ids.clone()
+    }
+}
+
+impl AffectedIds for Vec<u32> {
+    fn affected_ids(&self) -> Vec<u32> {
+        self.clone()
+    }
+}
+
+impl<T> AffectedIds for (u32, T) {
+    fn affected_ids(&self) -> Vec<u32> {
+        vec![self.0]
+    }
+}
+
+struct ReadOnlyDatabase {
+    inner: Box<dyn Database>,
+}
+
+impl Database for ReadOnlyDatabase {
+    fn read_db(&self) -> Result<DBState> {
+        self.inner.read_db()
+    }
+
+    fn write_db(&self, _db_state: &DBState) -> Result<()> {
+        Err(JiraError::ReadOnly)
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.inner.flush()
+    }
+
+    fn location(&self) -> Option<String> {
+        self.inner.location()
+    }
+}
+
+struct BatchedDatabase {
+    inner: Box<dyn Database>,
+    pending: Mutex<Option<DBState>>,
+}
+
+impl BatchedDatabase {
+    fn new(inner: Box<dyn Database>) -> Self {
+        Self {
+            inner,
+            pending: Mutex::new(None),
+        }
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, Option<DBState>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Database for BatchedDatabase {
+    fn read_db(&self) -> Result<DBState> {
+        match &*self.pending() {
+            Some(state) => Ok(state.clone()),
+            None => self.inner.read_db(),
+        }
+    }
+
+    fn write_db(&self, db_state: &DBState) -> Result<()> {
+        *self.pending() = Some(db_state.clone());
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        let mut pending = self.pending();
+        if let Some(state) = pending.as_ref() {
+            self.inner.write_db(state)?;
+        }
+        pending.take();
+
+        self.inner.flush()
+    }
+
+    fn location(&self) -> Option<String> {
+        self.inner.location()
+    }
+}
+
+struct JSONFileDatabase {
+    pub file_path: String,
+    pub compact: bool,
+}
+
+impl JSONFileDatabase {
+    fn create_if_missing(&self) -> Result<()> {
+        let path = Path::new(&self.file_path);
+        if path.exists() {
+            return Ok(());
+        }
+        let create = || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            self.write_db(&DBState::default())
+        };
+
+        create().map_err(|error| storage_error(error, self.location()))
+    }
+
+    fn ensure_exists(&self) -> Result<()> {
+        match Path::new(&self.file_path).exists() {
+            true => Ok(()),
+            false => Err(JiraError::IoError(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{} does not exist", self.file_path),
+            ))),
+        }
+    }
+}
+
+impl Database for JSONFileDatabase {
+    fn read_db(&self) -> Result<DBState> {
+        let file = File::open(&self.file_path)?;
+        let reader = BufReader::new(file);
+        let database: DBState = serde_json::from_reader(reader)?;
+
+        Ok(database)
+    }
+
+    fn write_db(&self, db_state: &DBState) -> Result<()> {
+        let content = match self.compact {
+            true => serde_json::to_string(db_state)?,
+            false => serde_json::to_string_pretty(db_state)?,
+        };
+        std::fs::write(&self.file_path, content)?;
+
+        Ok(())
+    }
+
+    fn location(&self) -> Option<String> {
+        Some(self.file_path.clone())
+    }
+}
+
+const DELTA_COMPACTION_THRESHOLD: usize = 200;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum DeltaEntry {
+    SetLastItemId {
+        id: u32,
+    },
+    SetProject {
+        name: Option<String>,
+        version: Option<String>,
+    },
+    PutEpic {
+        id: u32,
+        epic: Epic,
+    },
+    RemoveEpic {
+        id: u32,
+    },
+    PutStory {
+        id: u32,
+        story: Story,
+    },
+    RemoveStory {
+        id: u32,
+    },
+}
+
+impl DeltaEntry {
+    fn between(old: &DBState, new: &DBState) -> Vec<Self> {
+        let mut entries = vec![];
+
+        if old.last_item_id != new.last_item_id {
+            entries.push(Self::SetLastItemId {
+                id: new.last_item_id,
+            });
+        }
+        if (&old.project_name, &old.project_version) != (&new.project_name, &new.project_version) {
+            entries.push(Self::SetProject {
+                name: new.project_name.clone(),
+                version: new.project_version.clone(),
+            });
+        }
+        for (id, epic) in new.epics.iter().sorted_by_key(|(id, _)| **id) {
+            if old.epics.get(id) != Some(epic) {
+                entries.push(Self::PutEpic {
+                    id: *id,
+                    epic: epic.clone(),
+                });
+            }
+        }
+        for id in old.epics.keys().sorted() {
+            if !new.epics.contains_key(id) {
+                entries.push(Self::RemoveEpic { id: *id });
+            }
+        }
+        for (id, story) in new.stories.iter().sorted_by_key(|(id, _)| **id) {
+            if old.stories.get(id) != Some(story) {
+                entries.push(Self::PutStory {
+                    id: *id,
+                    story: story.clone(),
+                });
+            }
+        }
+        for id in old.stories.keys().sorted() {
+            if !new.stories.contains_key(id) {
+                entries.push(Self::RemoveStory { id: *id });
+            }
+        }
+
+        entries
+    }
+
+    fn apply(self, db_state: &mut DBState) {
+        match self {
+            Self::SetLastItemId { id } => db_state.last_item_id = id,
+            Self::SetProject { name, version } => {
+                db_state.project_name = name;
+                db_state.project_version = version;
+            }
+            Self::PutEpic { id, epic } => {
+                db_state.epics.insert(id, epic);
+            }
+            Self::RemoveEpic { id } => {
+                db_state.epics.remove(&id);
+            }
+            Self::PutStory { id, story } => {
+                db_state.stories.insert(id, story);
+            }
+            Self::RemoveStory { id } => {
+                db_state.stories.remove(&id);
+            }
+        }
+    }
+}
+
+struct DeltaDatabase {
+    snapshot: JSONFileDatabase,
+    log_path: String,
+    compaction_threshold: usize,
+    loaded: Mutex<Option<(DBState, usize)>>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::test_utils::MockDB;
-    use super::*;
+impl DeltaDatabase {
+    fn new(snapshot: JSONFileDatabase) -> Self {
+        Self {
+            log_path: format!("{}.log", snapshot.file_path),
+            snapshot,
+            compaction_threshold: DELTA_COMPACTION_THRESHOLD,
+            loaded: Mutex::new(None),
+        }
+    }
+
+    fn loaded(&self) -> Result<MutexGuard<'_, Option<(DBState, usize)>>> {
+        let mut loaded = self.loaded.lock().unwrap_or_else(PoisonError::into_inner);
+        if loaded.is_none() {
+            *loaded = Some(self.replay()?);
+        }
+
+        Ok(loaded)
+    }
+
+    fn replay(&self) -> Result<(DBState, usize)> {
+        let mut db_state = self.snapshot.read_db()?;
+        let mut entries = 0;
+
+        if Path::new(&self.log_path).exists() {
+            for line in BufReader::new(File::open(&self.log_path)?).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                serde_json::from_str::<DeltaEntry>(&line)?.apply(&mut db_state);
+                entries += 1;
+            }
+        }
+
+        Ok((db_state, entries))
+    }
+
+    fn compact(&self, db_state: &DBState) -> Result<()> {
+        self.snapshot.write_db(db_state)?;
+        File::create(&self.log_path)?;
+
+        Ok(())
+    }
+}
+
+impl Database for DeltaDatabase {
+    fn read_db(&self) -> Result<DBState> {
+        let loaded = self.loaded()?;
+        let (db_state, _) = loaded.as_ref().expect("delta log is loaded");
+
+        Ok(db_state.clone())
+    }
+
+    fn write_db(&self, db_state: &DBState) -> Result<()> {
+        let mut loaded = self.loaded()?;
+        let (current, entries) = loaded.as_mut().expect("delta log is loaded");
+
+        let changes = DeltaEntry::between(current, db_state);
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        if *entries + changes.len() >= self.compaction_threshold {
+            self.compact(db_state)?;
+            *entries = 0;
+        } else {
+            let mut log = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.log_path)?;
+            for change in &changes {
+                writeln!(log, "{}", serde_json::to_string(change)?)?;
+            }
+            *entries += changes.len();
+        }
+        *current = db_state.clone();
+
+        Ok(())
+    }
+
+    fn location(&self) -> Option<String> {
+        self.snapshot.location()
+    }
+}
+
+pub struct SharedJiraDatabase {
+    inner: RwLock<JiraDatabase>,
+}
+
+impl SharedJiraDatabase {
+    pub fn new(database: JiraDatabase) -> Self {
+        Self {
+            inner: RwLock::new(database),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, JiraDatabase> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, JiraDatabase> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn read_db(&self) -> Result<DBState> {
+        self.read().read_db()
+    }
+
+    pub fn stats(&self) -> Result<BoardStats> {
+        self.read().stats()
+    }
+
+    pub fn epic_progress(&self, epic_id: u32) -> Result<(usize, usize)> {
+        self.read().epic_progress(epic_id)
+    }
+
+    pub fn epic_percent_complete(&self, epic_id: u32) -> Result<u8> {
+        self.read().epic_percent_complete(epic_id)
+    }
+
+    pub fn set_percent_complete(&self, story_id: u32, percent: u8) -> Result<()> {
+        self.write().set_percent_complete(story_id, percent)
+    }
+
+    pub fn next_actionable_story(&self, epic_id: u32) -> Result<Option<u32>> {
+        self.read().next_actionable_story(epic_id)
+    }
+
+    pub fn export_epic_as_issue(&self, epic_id: u32) -> Result<String> {
+        self.read().export_epic_as_issue(epic_id)
+    }
+
+    pub fn export_timeline(&self) -> Result<String> {
+        self.read().export_timeline()
+    }
+
+    pub fn completion_ratio(&self) -> Result<f64> {
+        self.read().completion_ratio()
+    }
+
+    pub fn stories_by_status(&self, status: Status) -> Result<Vec<(u32, u32)>> {
+        self.read().stories_by_status(status)
+    }
+
+    pub fn ready_queue(&self) -> Result<Vec<(u32, u32)>> {
+        self.read().ready_queue()
+    }
+
+    pub fn standup_report(&self) -> Result<String> {
+        self.read().standup_report()
+    }
+
+    pub fn stale_stories(&self, days: u32) -> Result<Vec<u32>> {
+        self.read().stale_stories(days)
+    }
+
+    pub fn find_by_slug(&self, slug: &str) -> Result<Option<ItemRef>> {
+        self.read().find_by_slug(slug)
+    }
+
+    pub fn resolve_reference(&self, input: &str) -> Result<ResolveResult> {
+        self.read().resolve_reference(input)
+    }
+
+    pub fn transaction<R>(&self, f: impl FnOnce(&mut DBState) -> Result<R>) -> Result<R> {
+        self.write().transaction(f)
+    }
+
+    pub fn reset(&self, keep_settings: bool, id_start: Option<u32>) -> Result<()> {
+        self.write().reset(keep_settings, id_start)
+    }
+
+    pub fn set_project(&self, name: Option<String>, version: Option<String>) -> Result<()> {
+        self.write().set_project(name, version)
+    }
+
+    pub fn repair_item_id(&self) -> Result<bool> {
+        self.write().repair_item_id()
+    }
+
+    pub fn flush(&self) -> Result<()> {
+        self.write().flush()
+    }
+
+    pub fn create_epic(&self, epic: Epic) -> Result<u32> {
+        self.write().create_epic(epic)
+    }
+
+    pub fn ensure_epic(&self, name: &str, description: &str) -> Result<u32> {
+        self.write().ensure_epic(name, description)
+    }
+
+    pub fn create_story(&self, story: Story, epic_id: u32) -> Result<u32> {
+        self.write().create_story(story, epic_id)
+    }
+
+    pub fn import_stories_csv(&self, epic_id: u32, path: &str) -> Result<CsvImport> {
+        self.write().import_stories_csv(epic_id, path)
+    }
+
+    pub fn delete_epic(&self, epic_id: u32) -> Result<()> {
+        self.write().delete_epic(epic_id)
+    }
+
+    pub fn delete_epic_keep_stories(&self, epic_id: u32, reassign_to: Option<u32>) -> Result<()> {
+        self.write().delete_epic_keep_stories(epic_id, reassign_to)
+    }
+
+    pub fn merge_epics(&self, source: u32, dest: u32) -> Result<()> {
+        self.write().merge_epics(source, dest)
+    }
+
+    pub fn delete_story(&self, epic_id: u32, story_id: u32) -> Result<()> {
+        self.write().delete_story(epic_id, story_id)
+    }
+
+    pub fn update_epic_status(&self, epic_id: u32, status: Status) -> Result<()> {
+        self.write().update_epic_status(epic_id, status)
+    }
+
+    pub fn closed_stories_reopened_by(
+        &self,
+        epic_id: u32,
+        status: &Status,
+    ) -> Result<Option<usize>> {
+        self.read().closed_stories_reopened_by(epic_id, status)
+    }
+
+    pub fn set_epic_status(&self, epic_id: u32, status: Status, force: bool) -> Result<()> {
+        self.write().set_epic_status(epic_id, status, force)
+    }
+
+    pub fn edit_epic(&self, epic_id: u32, edit: ItemEdit) -> Result<()> {
+        self.write().edit_epic(epic_id, edit)
+    }
+
+    pub fn set_statuses(&self, updates: Vec<(ItemRef, Status)>) -> Result<()> {
+        self.write().set_statuses(updates)
+    }
+
+    pub fn bulk_transition(&self, epic_id: u32, from: Status, to: Status) -> Result<usize> {
+        self.write().bulk_transition(epic_id, from, to)
+    }
+
+    pub fn close_epic_cascade(&self, epic_id: u32) -> Result<()> {
+        self.write().close_epic_cascade(epic_id)
+    }
+
+    pub fn add_dod_item(&self, epic_id: u32, name: String) -> Result<()> {
+        self.write().add_dod_item(epic_id, name)
+    }
+
+    pub fn toggle_dod_item(&self, epic_id: u32, index: usize) -> Result<bool> {
+        self.write().toggle_dod_item(epic_id, index)
+    }
+
+    pub fn edit_story(&self, story_id: u32, edit: ItemEdit) -> Result<()> {
+        self.write().edit_story(story_id, edit)
+    }
+
+    pub fn replace_text(
+        &self,
+        find: &str,
+        replace: &str,
+        fields: TextFields,
+        case_sensitive: bool,
+    ) -> Result<usize> {
+        self.write()
+            .replace_text(find, replace, fields, case_sensitive)
+    }
+
+    pub fn update_story_status(&self, story_id: u32, status: Status) -> Result<()> {
+        self.write().update_story_status(story_id, status)
+    }
+
+    pub fn reopen_story(&self, story_id: u32, reason: String) -> Result<()> {
+        self.write().reopen_story(story_id, reason)
+    }
+
+    pub fn snooze_story(&self, story_id: u32, until: NaiveDate) -> Result<()> {
+        self.write().snooze_story(story_id, until)
+    }
+
+    pub fn set_due_date(&self, story_id: u32, due_date: Option<NaiveDate>) -> Result<()> {
+        self.write().set_due_date(story_id, due_date)
+    }
+
+    pub fn extract_subtasks(&self, story_id: u32) -> Result<usize> {
+        self.write().extract_subtasks(story_id)
+    }
+
+    pub fn add_subtask(&self, story_id: u32, name: String) -> Result<()> {
+        self.write().add_subtask(story_id, name)
+    }
+
+    pub fn toggle_epic_star(&self, epic_id: u32) -> Result<bool> {
+        self.write().toggle_epic_star(epic_id)
+    }
+
+    pub fn toggle_story_pin(&self, story_id: u32) -> Result<bool> {
+        self.write().toggle_story_pin(story_id)
+    }
+
+    pub fn add_assignee(&self, story_id: u32, name: &str) -> Result<bool> {
+        self.write().add_assignee(story_id, name)
+    }
+
+    pub fn remove_assignee(&self, story_id: u32, name: &str) -> Result<bool> {
+        self.write().remove_assignee(story_id, name)
+    }
+
+    pub fn stories_assigned_to(&self, name: &str) -> Result<Vec<u32>> {
+        self.read().stories_assigned_to(name)
+    }
+
+    pub fn track_time(
+        &self,
+        story_id: u32,
+        estimate_hours: Option<f32>,
+        actual_hours: Option<f32>,
+    ) -> Result<()> {
+        self.write()
+            .track_time(story_id, estimate_hours, actual_hours)
+    }
+
+    pub fn time_rollup(&self, epic_id: u32) -> Result<(f32, f32)> {
+        self.read().time_rollup(epic_id)
+    }
+
+    pub fn critical_path(&self, epic_id: u32) -> Result<Vec<u32>> {
+        self.read().critical_path(epic_id)
+    }
+
+    pub fn toggle_subtask(&self, story_id: u32, index: usize) -> Result<()> {
+        self.write().toggle_subtask(story_id, index)
+    }
+}
+
+pub fn db_schema() -> Result<String> {
+    let schema = schemars::schema_for!(DBState);
+    let content = serde_json::to_string_pretty(&schema)?;
+
+    Ok(content)
+}
+
+pub mod test_utils {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    pub struct MockDB {
+        last_written_state: Mutex<DBState>,
+    }
+
+    impl MockDB {
+        pub fn new() -> Self {
+            Self {
+                last_written_state: Mutex::new(DBState {
+                    last_item_id: 0,
+                    epics: HashMap::new(),
+                    stories: HashMap::new(),
+                    project_name: None,
+                    project_version: None,
+                }),
+            }
+        }
+    }
+
+    impl Default for MockDB {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Database for MockDB {
+        fn read_db(&self) -> Result<DBState> {
+            let state = self.last_written_state.lock().unwrap().clone();
+            Ok(state)
+        }
+
+        fn write_db(&self, db_state: &DBState) -> Result<()> {
+            let latest_state = &self.last_written_state;
+            *latest_state.lock().unwrap() = db_state.clone();
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::test_utils::MockDB;
+    use super::*;
+    use crate::models::Subtask;
+
+    #[test]
+    fn seed_id_start_should_set_first_id_on_empty_board() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        db.seed_id_start(1000).unwrap();
+
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert_eq!(epic_id, 1000);
+    }
+
+    #[test]
+    fn seed_id_start_should_be_noop_on_non_empty_board() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let _ = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        db.seed_id_start(1000).unwrap();
+
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert_eq!(epic_id, 2);
+    }
+
+    #[test]
+    fn seed_id_start_should_not_lower_the_counter_of_an_emptied_board() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        db.transaction(|db| {
+            db.last_item_id = 50;
+            Ok(())
+        })
+        .unwrap();
+        db.seed_id_start(10).unwrap();
+
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert_eq!(epic_id, 51);
+    }
+
+    #[test]
+    fn import_stories_csv_should_create_stories_under_epic() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stories.csv");
+        std::fs::write(
+            &path,
+            "name,description,status\n\
+             Login page,\"Email, password\",in progress\n\
+             \n\
+             \"Say \"\"hi\"\"\",\"two\nlines\",someday\n\
+             Logout,,\n",
+        )
+        .unwrap();
+
+        let import = db
+            .import_stories_csv(epic_id, path.to_str().unwrap())
+            .unwrap();
+        let db_state = db.read_db().unwrap();
+        let imported = import
+            .story_ids
+            .iter()
+            .map(|id| {
+                let story = &db_state.stories[id];
+                (
+                    story.name.as_str(),
+                    story.description.as_str(),
+                    &story.status,
+                )
+            })
+            .collect_vec();
+
+        assert_eq!(
+            imported,
+            vec![
+                ("Login page", "Email, password", &Status::InProgress),
+                ("Say \"hi\"", "two\nlines", &Status::Open),
+                ("Logout", "", &Status::Open),
+            ]
+        );
+        assert_eq!(db_state.epics[&epic_id].stories, import.story_ids);
+        assert_eq!(import.warnings.len(), 1);
+        assert_eq!(import.warnings[0].0, 4);
+
+        std::fs::write(&path, "First,,open\n,no name,open\n").unwrap();
+        assert!(matches!(
+            db.import_stories_csv(epic_id, path.to_str().unwrap()),
+            Err(JiraError::MalformedCsv { line: 2, .. })
+        ));
+        assert_eq!(db.read_db().unwrap().stories.len(), 3);
+    }
+
+    #[test]
+    fn next_actionable_story_should_skip_started_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert_eq!(db.next_actionable_story(epic_id).unwrap(), None);
+
+        let started = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let open = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_story_status(started, Status::InProgress).unwrap();
+
+        assert_eq!(db.next_actionable_story(epic_id).unwrap(), Some(open));
+
+        db.update_story_status(open, Status::Closed).unwrap();
+
+        assert_eq!(db.next_actionable_story(epic_id).unwrap(), None);
+        assert!(matches!(
+            db.next_actionable_story(999),
+            Err(JiraError::EpicNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn set_project_should_round_trip_through_the_database() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        assert_eq!(db.read_db().unwrap().project_label(), None);
+
+        db.set_project(Some("jirrah".to_owned()), Some("0.2".to_owned()))
+            .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.project_name, Some("jirrah".to_owned()));
+        assert_eq!(db_state.project_version, Some("0.2".to_owned()));
+        assert_eq!(db_state.project_label(), Some("jirrah 0.2".to_owned()));
+
+        db.set_project(None, Some("v3".to_owned())).unwrap();
+        assert_eq!(db.read_db().unwrap().project_label(), Some("v3".to_owned()));
+    }
+
+    #[test]
+    fn logged_should_append_one_line_per_operation() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("operations.log");
+        let db =
+            JiraDatabase::with_database(Box::new(MockDB::new())).logged(log_path.to_str().unwrap());
+
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        let lines = log.lines().collect_vec();
+        assert_eq!(lines.len(), 1);
+
+        let entry: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(entry["operation"], serde_json::json!("create_epic"));
+        assert_eq!(entry["ids"], serde_json::json!([epic_id]));
+        assert_eq!(entry["ok"], serde_json::json!(true));
+    }
+
+    #[test]
+    fn logged_should_record_operations_that_fail_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("operations.log");
+        let db =
+            JiraDatabase::with_database(Box::new(MockDB::new())).logged(log_path.to_str().unwrap());
+
+        assert!(db
+            .create_story(Story::new("".to_owned(), "".to_owned()), 999)
+            .is_err());
+
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        let lines = log.lines().collect_vec();
+        assert_eq!(lines.len(), 1);
+
+        let entry: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(entry["operation"], serde_json::json!("create_story"));
+        assert_eq!(entry["ids"], serde_json::json!([999]));
+        assert_eq!(entry["ok"], serde_json::json!(false));
+        assert_eq!(
+            entry["error"],
+            serde_json::json!("No epic found with id 999")
+        );
+    }
+
+    #[test]
+    fn logged_should_not_fail_when_the_log_is_unwritable() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("missing").join("operations.log");
+        let db =
+            JiraDatabase::with_database(Box::new(MockDB::new())).logged(log_path.to_str().unwrap());
+
+        assert!(db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .is_ok());
+        assert!(!log_path.exists());
+    }
+
+    #[test]
+    fn strict_dod_should_block_closing_until_every_item_is_checked() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new())).with_strict_dod(true);
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        db.add_dod_item(epic_id, "Docs written".to_owned()).unwrap();
+        db.add_dod_item(epic_id, "Release notes".to_owned())
+            .unwrap();
+        assert!(db.toggle_dod_item(epic_id, 0).unwrap());
+
+        let error = db.update_epic_status(epic_id, Status::Closed).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "EPIC-{} cannot be closed until its definition of done is checked: Release notes",
+                epic_id
+            )
+        );
+        assert!(db.close_epic_cascade(epic_id).is_err());
+        assert_eq!(db.read_db().unwrap().epics[&epic_id].status, Status::Open);
+
+        assert!(db.toggle_dod_item(epic_id, 1).unwrap());
+        db.update_epic_status(epic_id, Status::Closed).unwrap();
+        assert_eq!(db.read_db().unwrap().epics[&epic_id].status, Status::Closed);
+        assert!(matches!(
+            db.toggle_dod_item(epic_id, 2),
+            Err(JiraError::InvalidOperation(_))
+        ));
+    }
+
+    #[test]
+    fn closing_with_unchecked_dod_should_be_allowed_outside_strict_mode() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        db.add_dod_item(epic_id, "Docs written".to_owned()).unwrap();
+
+        db.update_epic_status(epic_id, Status::Closed).unwrap();
+        assert_eq!(db.read_db().unwrap().epics[&epic_id].status, Status::Closed);
+    }
+
+    #[test]
+    fn set_epic_status_should_need_force_to_reopen_a_closed_epic() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::Closed).unwrap();
+        db.set_epic_status(epic_id, Status::Closed, false).unwrap();
+
+        assert_eq!(
+            db.closed_stories_reopened_by(epic_id, &Status::Open)
+                .unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            db.closed_stories_reopened_by(epic_id, &Status::Resolved)
+                .unwrap(),
+            None
+        );
+        assert!(matches!(
+            db.set_epic_status(epic_id, Status::InProgress, false),
+            Err(JiraError::InvalidOperation(_))
+        ));
+        assert_eq!(db.read_db().unwrap().epics[&epic_id].status, Status::Closed);
+
+        db.set_epic_status(epic_id, Status::InProgress, true)
+            .unwrap();
+
+        assert_eq!(
+            db.read_db().unwrap().epics[&epic_id].status,
+            Status::InProgress
+        );
+    }
+
+    #[test]
+    fn reset_should_clear_items_and_keep_settings_when_asked() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let board = || {
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.set_project(Some("jirrah".to_owned()), Some("0.2".to_owned()))
+                .unwrap();
+        };
+
+        board();
+        db.reset(true, Some(100)).unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert!(db_state.epics.is_empty());
+        assert!(db_state.stories.is_empty());
+        assert_eq!(db_state.last_item_id, 99);
+        assert_eq!(db_state.project_label(), Some("jirrah 0.2".to_owned()));
+
+        board();
+        db.reset(false, None).unwrap();
+
+        assert_eq!(db.read_db().unwrap(), DBState::default());
+    }
+
+    #[test]
+    fn repair_item_id_should_raise_a_stale_counter() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(!db.repair_item_id().unwrap());
+        assert_eq!(db.read_db().unwrap().last_item_id, story_id);
+
+        db.transaction(|state| {
+            state.last_item_id = 0;
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(db.repair_item_id().unwrap());
+        assert_eq!(db.read_db().unwrap().last_item_id, story_id);
+        assert!(!db.repair_item_id().unwrap());
+    }
+
+    #[test]
+    fn transaction_should_commit_all_changes_on_success() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+
+        let epic_id = db
+            .transaction(|state| {
+                let epic_id = next_item_id(state)?;
+                state
+                    .epics
+                    .insert(epic_id, Epic::new("".to_owned(), "".to_owned()));
+                state.last_item_id = epic_id;
+                Ok(epic_id)
+            })
+            .unwrap();
+
+        assert!(db.read_db().unwrap().epics.contains_key(&epic_id));
+    }
+
+    #[test]
+    fn transaction_should_leave_db_unchanged_on_error() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let before = db.read_db().unwrap();
+
+        let result: Result<()> = db.transaction(|state| {
+            state.epics.remove(&epic_id);
+            state
+                .stories
+                .insert(99, Story::new("".to_owned(), "".to_owned()));
+            Err(JiraError::StoryNotFound(100))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(db.read_db().unwrap(), before);
+    }
+
+    #[test]
+    fn stats_should_count_epics_and_open_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let _ = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let _ = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let closed_story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_story_status(closed_story_id, Status::Closed)
+            .unwrap();
+
+        let stats = db.stats().unwrap();
+
+        assert_eq!(
+            stats,
+            BoardStats {
+                epic_count: 2,
+                story_count: 2,
+                open_story_count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn epic_progress_should_count_done_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_ids: Vec<u32> = (0..3)
+            .map(|_| {
+                db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap()
+            })
+            .collect();
+        db.update_story_status(story_ids[0], Status::Closed)
+            .unwrap();
+        db.update_story_status(story_ids[1], Status::Resolved)
+            .unwrap();
+
+        assert_eq!(db.epic_progress(epic_id).unwrap(), (2, 3));
+        assert!(matches!(
+            db.epic_progress(999),
+            Err(JiraError::EpicNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn epic_percent_complete_should_weigh_partial_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_ids: Vec<u32> = (0..4)
+            .map(|_| {
+                db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(db.epic_percent_complete(epic_id).unwrap(), 0);
+
+        db.update_story_status(story_ids[0], Status::Closed)
+            .unwrap();
+        db.set_percent_complete(story_ids[1], 50).unwrap();
+        db.set_percent_complete(story_ids[2], 30).unwrap();
+
+        assert_eq!(db.epic_percent_complete(epic_id).unwrap(), 45);
+        assert_eq!(db.epic_progress(epic_id).unwrap(), (1, 4));
+        assert!(matches!(
+            db.set_percent_complete(story_ids[3], 101),
+            Err(JiraError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            db.epic_percent_complete(999),
+            Err(JiraError::EpicNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn set_percent_complete_should_resolve_finished_stories_when_enabled() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        db.set_percent_complete(story_id, 100).unwrap();
+        assert_eq!(
+            db.read_db().unwrap().stories[&story_id].status,
+            Status::Open
+        );
+
+        let db = db.with_resolve_on_complete(true);
+        db.set_percent_complete(story_id, 100).unwrap();
+        assert_eq!(
+            db.read_db().unwrap().stories[&story_id].status,
+            Status::Resolved
+        );
+    }
+
+    #[test]
+    fn stale_stories_should_flag_old_open_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let long_ago = Utc::now() - Duration::days(30);
+        let mut old = Story::new("".to_owned(), "".to_owned());
+        old.status_history = vec![(Status::Open, long_ago)];
+        old.set_status(Status::InProgress, long_ago);
+        old.updated_at = Some(long_ago);
+        let mut legacy = old.clone();
+        legacy.updated_at = None;
+        let mut old_closed = Story::new("".to_owned(), "".to_owned());
+        old_closed.status_history = vec![(Status::Open, long_ago)];
+        old_closed.set_status(Status::Closed, long_ago);
+        old_closed.updated_at = Some(long_ago);
+
+        let old_id = db.create_story(old, epic_id).unwrap();
+        db.create_story(old_closed, epic_id).unwrap();
+        let fresh_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_story_status(fresh_id, Status::InProgress)
+            .unwrap();
+        let legacy_id = db.create_story(legacy, epic_id).unwrap();
+
+        assert_eq!(db.stale_stories(14).unwrap(), vec![old_id, legacy_id]);
+    }
+
+    #[test]
+    fn stale_stories_should_count_edits_without_a_status_change() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let long_ago = Utc::now() - Duration::days(30);
+        let mut story = Story::new("".to_owned(), "".to_owned());
+        story.status_history = vec![(Status::Open, long_ago)];
+        story.updated_at = Some(long_ago);
+        let story_id = db.create_story(story, epic_id).unwrap();
+
+        assert_eq!(db.stale_stories(14).unwrap(), vec![story_id]);
+
+        db.edit_story(
+            story_id,
+            ItemEdit {
+                description: Some("Now with steps to reproduce".to_owned()),
+                ..ItemEdit::default()
+            },
+        )
+        .unwrap();
+
+        assert!(db.stale_stories(14).unwrap().is_empty());
+    }
+
+    #[test]
+    fn ready_queue_should_list_open_stories_across_epics() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let first = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let second = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story = |epic_id| {
+            db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap()
+        };
+        let ready = story(second);
+        let started = story(first);
+        let snoozed = story(first);
+        let closed = story(second);
+        let also_ready = story(first);
+        db.update_story_status(started, Status::InProgress).unwrap();
+        db.update_story_status(closed, Status::Closed).unwrap();
+        let tomorrow = Local::now().date_naive() + Duration::days(1);
+        db.snooze_story(snoozed, tomorrow).unwrap();
+
+        assert_eq!(
+            db.ready_queue().unwrap(),
+            vec![(second, ready), (first, also_ready)]
+        );
+    }
+
+    #[test]
+    fn standup_report_should_group_active_stories_by_assignee() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let yesterday = Utc::now() - Duration::days(1);
+        let long_ago = Utc::now() - Duration::days(30);
+        let story = |name: &str, assignees: &[&str], status: Status, at: DateTime<Utc>| {
+            let mut story = Story::new(name.to_owned(), "".to_owned());
+            story.status_history = vec![(Status::Open, long_ago)];
+            story.set_status(status, at);
+            for assignee in assignees {
+                story.add_assignee(assignee);
+            }
+            db.create_story(story, epic_id).unwrap()
+        };
+
+        let docs = story("Docs", &["alice", "Bob"], Status::InProgress, long_ago);
+        let tests = story("Tests", &["bob"], Status::Closed, yesterday);
+        story("Release", &["carol"], Status::Closed, long_ago);
+        story("Unowned", &[], Status::InProgress, long_ago);
+
+        assert_eq!(
+            db.standup_report().unwrap(),
+            format!(
+                "# Standup {}\n\n\
+                 ## alice\n\n\
+                 - STORY-{docs} Docs (IN PROGRESS)\n\n\
+                 ## Bob\n\n\
+                 - STORY-{docs} Docs (IN PROGRESS)\n\
+                 - STORY-{tests} Tests (Closed, updated yesterday)\n",
+                Local::now().date_naive()
+            )
+        );
+    }
+
+    #[test]
+    fn ensure_epic_should_create_missing_epic() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+
+        let epic_id = db.ensure_epic("Launch", "v1").unwrap();
+
+        let db_state = db.read_db().unwrap();
+        let epic = db_state.epics.get(&epic_id).unwrap();
+        assert_eq!(epic.name, "Launch");
+        assert_eq!(epic.description, "v1");
+        assert_eq!(db_state.last_item_id, epic_id);
+    }
+
+    #[test]
+    fn ensure_epic_should_find_existing_epic_ignoring_case() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("Launch".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert_eq!(db.ensure_epic("LAUNCH", "other").unwrap(), epic_id);
+        assert_eq!(db.ensure_epic("launch", "").unwrap(), epic_id);
+        assert_eq!(db.read_db().unwrap().epics.len(), 1);
+    }
+
+    #[test]
+    fn ensure_epic_should_prefer_lowest_id_on_duplicate_names() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let first_id = db
+            .create_epic(Epic::new("Launch".to_owned(), "".to_owned()))
+            .unwrap();
+        db.create_epic(Epic::new("launch".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert_eq!(db.ensure_epic("Launch", "").unwrap(), first_id);
+    }
+
+    #[test]
+    fn set_statuses_should_apply_every_update() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        db.set_statuses(vec![
+            (ItemRef::Epic(epic_id), Status::InProgress),
+            (ItemRef::Any(story_id), Status::Resolved),
+        ])
+        .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(
+            db_state.epics.get(&epic_id).unwrap().status,
+            Status::InProgress
+        );
+        assert_eq!(
+            db_state.stories.get(&story_id).unwrap().status,
+            Status::Resolved
+        );
+    }
+
+    #[test]
+    fn set_statuses_should_change_nothing_if_any_item_is_missing() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let before = db.read_db().unwrap();
+
+        let result = db.set_statuses(vec![
+            (ItemRef::Epic(epic_id), Status::Closed),
+            (ItemRef::Story(999), Status::Closed),
+            (ItemRef::Story(story_id), Status::Closed),
+        ]);
+
+        assert!(matches!(result, Err(JiraError::StoryNotFound(999))));
+        assert_eq!(db.read_db().unwrap(), before);
+    }
+
+    #[test]
+    fn bulk_transition_should_only_move_matching_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let other_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let statuses = [
+            Status::Resolved,
+            Status::Open,
+            Status::Resolved,
+            Status::InProgress,
+        ];
+        let story_ids: Vec<u32> = statuses
+            .iter()
+            .map(|status| {
+                let story_id = db
+                    .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap();
+                db.update_story_status(story_id, status.clone()).unwrap();
+                story_id
+            })
+            .collect();
+        let other_story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), other_epic_id)
+            .unwrap();
+        db.update_story_status(other_story_id, Status::Resolved)
+            .unwrap();
+
+        let changed = db
+            .bulk_transition(epic_id, Status::Resolved, Status::Closed)
+            .unwrap();
+
+        assert_eq!(changed, 2);
+        let db_state = db.read_db().unwrap();
+        let status_of = |story_id: &u32| db_state.stories.get(story_id).unwrap().status.clone();
+        assert_eq!(
+            story_ids.iter().map(status_of).collect_vec(),
+            vec![
+                Status::Closed,
+                Status::Open,
+                Status::Closed,
+                Status::InProgress
+            ]
+        );
+        assert_eq!(status_of(&other_story_id), Status::Resolved);
+        assert!(matches!(
+            db.bulk_transition(999, Status::Open, Status::Closed),
+            Err(JiraError::EpicNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn close_epic_cascade_should_close_epic_and_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_ids: Vec<u32> = (0..3)
+            .map(|_| {
+                db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap()
+            })
+            .collect();
+        db.update_story_status(story_ids[0], Status::InProgress)
+            .unwrap();
+        db.update_story_status(story_ids[1], Status::Closed)
+            .unwrap();
+
+        db.close_epic_cascade(epic_id).unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics.get(&epic_id).unwrap().status, Status::Closed);
+        for story_id in &story_ids {
+            let story = db_state.stories.get(story_id).unwrap();
+            assert_eq!(story.status, Status::Closed);
+            assert_eq!(
+                story
+                    .status_history
+                    .iter()
+                    .filter(|(status, _)| *status == Status::Closed)
+                    .count(),
+                1
+            );
+        }
+        assert!(matches!(
+            db.close_epic_cascade(999),
+            Err(JiraError::EpicNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn extract_subtasks_should_move_checklist_lines_into_subtasks() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(
+                Story::new(
+                    "".to_owned(),
+                    "Ship the docs site.\n- write intro\n  - [x] pick theme\nThen announce it.\n"
+                        .to_owned(),
+                ),
+                epic_id,
+            )
+            .unwrap();
+
+        assert_eq!(db.extract_subtasks(story_id).unwrap(), 2);
+
+        let story = db.read_db().unwrap().stories.remove(&story_id).unwrap();
+        assert_eq!(story.description, "Ship the docs site.\nThen announce it.");
+        assert_eq!(
+            story.subtasks,
+            vec![
+                Subtask {
+                    name: "write intro".to_owned(),
+                    done: false
+                },
+                Subtask {
+                    name: "pick theme".to_owned(),
+                    done: true
+                },
+            ]
+        );
+        assert!(matches!(
+            db.extract_subtasks(999),
+            Err(JiraError::StoryNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn reopen_story_should_only_reopen_done_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(matches!(
+            db.reopen_story(story_id, "".to_owned()),
+            Err(JiraError::InvalidOperation(_))
+        ));
+
+        db.update_story_status(story_id, Status::Resolved).unwrap();
+        db.reopen_story(story_id, "Fails on Safari".to_owned())
+            .unwrap();
+
+        let story = db.read_db().unwrap().stories.remove(&story_id).unwrap();
+        assert_eq!(story.status, Status::Open);
+        assert_eq!(
+            story
+                .status_history
+                .iter()
+                .map(|(status, _)| status.clone())
+                .collect_vec(),
+            vec![Status::Open, Status::Resolved, Status::Open]
+        );
+        assert_eq!(
+            story.reopen_reasons,
+            vec![(story.status_history[2].1, "Fails on Safari".to_owned())]
+        );
+        assert!(matches!(
+            db.reopen_story(999, "".to_owned()),
+            Err(JiraError::StoryNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn snooze_story_should_require_a_future_date() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let today = Local::now().date_naive();
+        let tomorrow = today.succ_opt().unwrap();
+
+        assert!(matches!(
+            db.snooze_story(story_id, today),
+            Err(JiraError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            db.snooze_story(999, tomorrow),
+            Err(JiraError::StoryNotFound(999))
+        ));
+
+        db.snooze_story(story_id, tomorrow).unwrap();
+        let story = db.read_db().unwrap().stories.remove(&story_id).unwrap();
+        assert_eq!(story.snoozed_until, Some(tomorrow));
+        assert!(story.is_snoozed(today));
+        assert!(!story.is_snoozed(tomorrow));
+    }
+
+    #[test]
+    fn export_epic_as_issue_should_check_done_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new(
+                "Launch".to_owned(),
+                "Everything needed for v1".to_owned(),
+            ))
+            .unwrap();
+        let story_ids: Vec<u32> = ["Docs", "Tests", "Release"]
+            .iter()
+            .map(|name| {
+                db.create_story(Story::new(name.to_string(), "".to_owned()), epic_id)
+                    .unwrap()
+            })
+            .collect();
+        db.update_story_status(story_ids[0], Status::Closed)
+            .unwrap();
+        db.update_story_status(story_ids[1], Status::InProgress)
+            .unwrap();
+        db.update_story_status(story_ids[2], Status::Resolved)
+            .unwrap();
+
+        assert_eq!(
+            db.export_epic_as_issue(epic_id).unwrap(),
+            "Everything needed for v1\n\n\
+             - [x] STORY-2 Docs\n\
+             - [ ] STORY-3 Tests\n\
+             - [x] STORY-4 Release\n"
+        );
+        assert!(matches!(
+            db.export_epic_as_issue(999),
+            Err(JiraError::EpicNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn export_timeline_should_order_stories_by_due_date() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_ids: Vec<u32> = ["Docs", "Tests", "Release"]
+            .iter()
+            .map(|name| {
+                db.create_story(Story::new(name.to_string(), "".to_owned()), epic_id)
+                    .unwrap()
+            })
+            .collect();
+        let date = |day| NaiveDate::from_ymd_opt(2026, 3, day).unwrap();
+        db.set_due_date(story_ids[0], Some(date(20))).unwrap();
+        db.set_due_date(story_ids[2], Some(date(10))).unwrap();
+        db.track_time(story_ids[0], Some(20.0), None).unwrap();
+
+        assert_eq!(
+            db.export_timeline().unwrap(),
+            "gantt\n    \
+             title Timeline\n    \
+             dateFormat YYYY-MM-DD\n    \
+             Release :story4, 2026-03-09, 2026-03-10\n    \
+             Docs :story2, 2026-03-17, 2026-03-20\n\
+             %% unscheduled\n\
+             %% STORY-3 Tests\n"
+        );
+        assert!(matches!(
+            db.set_due_date(999, None),
+            Err(JiraError::StoryNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn replace_text_should_update_names_and_descriptions() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new(
+                "Acme launch".to_owned(),
+                "Ship ACME to everyone".to_owned(),
+            ))
+            .unwrap();
+        let renamed = db
+            .create_story(
+                Story::new("Acme docs".to_owned(), "About acme".to_owned()),
+                epic_id,
+            )
+            .unwrap();
+        let untouched = db
+            .create_story(Story::new("Tests".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let fields = TextFields {
+            names: true,
+            descriptions: true,
+        };
+
+        assert_eq!(db.replace_text("Acme", "Zenith", fields, true).unwrap(), 2);
+        let state = db.read_db().unwrap();
+        assert_eq!(state.epics[&epic_id].name, "Zenith launch");
+        assert_eq!(state.epics[&epic_id].description, "Ship ACME to everyone");
+        assert_eq!(state.epics[&epic_id].slug, "zenith-launch");
+        assert_eq!(state.stories[&renamed].name, "Zenith docs");
+        assert_eq!(state.stories[&renamed].description, "About acme");
+
+        assert_eq!(db.replace_text("acme", "Zenith", fields, false).unwrap(), 2);
+        let state = db.read_db().unwrap();
+        assert_eq!(state.epics[&epic_id].description, "Ship Zenith to everyone");
+        assert_eq!(state.stories[&renamed].description, "About Zenith");
+        assert_eq!(state.stories[&untouched].name, "Tests");
+
+        let names_only = TextFields {
+            names: true,
+            descriptions: false,
+        };
+        assert_eq!(
+            db.replace_text("zenith", "Acme", names_only, false)
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            db.read_db().unwrap().stories[&renamed].description,
+            "About Zenith"
+        );
+        assert!(matches!(
+            db.replace_text("", "x", fields, true),
+            Err(JiraError::InvalidOperation(_))
+        ));
+    }
+
+    #[test]
+    fn completion_ratio_should_depend_on_done_statuses() {
+        let mut db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        for status in [
+            Status::Closed,
+            Status::Resolved,
+            Status::Open,
+            Status::InProgress,
+        ] {
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.update_story_status(story_id, status).unwrap();
+        }
+
+        assert_eq!(db.completion_ratio().unwrap(), 0.5);
+
+        db.done_statuses = vec![Status::Closed];
+
+        assert_eq!(db.completion_ratio().unwrap(), 0.25);
+    }
+
+    #[test]
+    fn resolve_reference_should_find_unique_match() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("Login flow".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("Signup page".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert_eq!(
+            db.resolve_reference("log").unwrap(),
+            ResolveResult::Unique(ItemRef::Epic(epic_id))
+        );
+        assert_eq!(
+            db.resolve_reference(&format!("STORY-{}", story_id))
+                .unwrap(),
+            ResolveResult::Unique(ItemRef::Story(story_id))
+        );
+        assert_eq!(
+            db.resolve_reference(&story_id.to_string()).unwrap(),
+            ResolveResult::Unique(ItemRef::Story(story_id))
+        );
+    }
+
+    #[test]
+    fn slugs_should_be_suffixed_on_collision_and_follow_renames() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let first = db
+            .create_epic(Epic::new("Login Flow".to_owned(), "".to_owned()))
+            .unwrap();
+        let second = db
+            .create_epic(Epic::new("login flow!".to_owned(), "".to_owned()))
+            .unwrap();
+        let third = db.ensure_epic("LOGIN  FLOW?", "").unwrap();
+        let story_id = db
+            .create_story(Story::new("Login flow".to_owned(), "".to_owned()), first)
+            .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics[&first].slug, "login-flow");
+        assert_eq!(db_state.epics[&second].slug, "login-flow-2");
+        assert_eq!(db_state.epics[&third].slug, "login-flow-3");
+        assert_eq!(db_state.stories[&story_id].slug, "login-flow");
+
+        assert_eq!(
+            db.find_by_slug("login-flow-2").unwrap(),
+            Some(ItemRef::Epic(second))
+        );
+        assert_eq!(
+            db.find_by_slug("login-flow").unwrap(),
+            Some(ItemRef::Epic(first))
+        );
+        assert_eq!(db.find_by_slug("signup").unwrap(), None);
+
+        let accented = db
+            .create_epic(Epic::new("Café launch".to_owned(), "".to_owned()))
+            .unwrap();
+        assert_eq!(db.read_db().unwrap().epics[&accented].slug, "cafe-launch");
+        assert_eq!(
+            db.find_by_slug("cafe-launch").unwrap(),
+            Some(ItemRef::Epic(accented))
+        );
+        assert_eq!(
+            db.find_by_slug("Café launch").unwrap(),
+            Some(ItemRef::Epic(accented))
+        );
+
+        db.edit_epic(
+            first,
+            ItemEdit {
+                name: Some("Signup".to_owned()),
+                ..ItemEdit::default()
+            },
+        )
+        .unwrap();
+        db.edit_story(
+            story_id,
+            ItemEdit {
+                description: Some("changed".to_owned()),
+                ..ItemEdit::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            db.find_by_slug("signup").unwrap(),
+            Some(ItemRef::Epic(first))
+        );
+        assert_eq!(
+            db.find_by_slug("login-flow").unwrap(),
+            Some(ItemRef::Story(story_id))
+        );
+        assert_eq!(
+            db.resolve_reference("login-flow-2").unwrap(),
+            ResolveResult::Unique(ItemRef::Epic(second))
+        );
+    }
+
+    #[test]
+    fn resolve_reference_should_list_ambiguous_matches() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("Login flow".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(
+                Story::new("Logout button".to_owned(), "".to_owned()),
+                epic_id,
+            )
+            .unwrap();
+
+        assert_eq!(
+            db.resolve_reference("LOG").unwrap(),
+            ResolveResult::Ambiguous(vec![ItemRef::Epic(epic_id), ItemRef::Story(story_id)])
+        );
+    }
+
+    #[test]
+    fn resolve_reference_should_return_none_without_matches() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let _ = db
+            .create_epic(Epic::new("Login flow".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert_eq!(
+            db.resolve_reference("billing").unwrap(),
+            ResolveResult::None
+        );
+        assert_eq!(db.resolve_reference("EPIC-7").unwrap(), ResolveResult::None);
+        assert_eq!(db.resolve_reference("").unwrap(), ResolveResult::None);
+    }
+
+    #[test]
+    fn update_story_status_should_append_status_history() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        db.update_story_status(story_id, Status::InProgress)
+            .unwrap();
+        db.update_story_status(story_id, Status::InProgress)
+            .unwrap();
+        db.update_story_status(story_id, Status::Closed).unwrap();
+
+        let history = db.read_db().unwrap().stories[&story_id]
+            .status_history
+            .clone();
+        let statuses = history
+            .iter()
+            .map(|(status, _)| status.clone())
+            .collect_vec();
+        assert_eq!(
+            statuses,
+            vec![Status::Open, Status::InProgress, Status::Closed]
+        );
+        assert!(history.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn cycle_time_should_span_first_start_to_final_completion() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let at = |hours| DateTime::UNIX_EPOCH + Duration::hours(hours);
+        let mut story = Story::new("".to_owned(), "".to_owned());
+        story.status_history = vec![
+            (Status::Open, at(0)),
+            (Status::InProgress, at(2)),
+            (Status::Open, at(5)),
+            (Status::InProgress, at(8)),
+            (Status::Resolved, at(10)),
+            (Status::InProgress, at(11)),
+            (Status::Resolved, at(20)),
+            (Status::Closed, at(26)),
+        ];
+        story.status = Status::Closed;
+        let story_id = db.create_story(story, epic_id).unwrap();
+
+        assert_eq!(db.cycle_time(story_id).unwrap(), Some(Duration::hours(18)));
+    }
+
+    #[test]
+    fn cycle_time_should_be_none_for_unfinished_story() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::InProgress)
+            .unwrap();
+
+        assert_eq!(db.cycle_time(story_id).unwrap(), None);
+        assert!(matches!(
+            db.cycle_time(999),
+            Err(JiraError::StoryNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn status_counts_should_match_story_distribution() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        for status in [
+            Status::InProgress,
+            Status::InProgress,
+            Status::Closed,
+            Status::Open,
+            Status::InProgress,
+        ] {
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.update_story_status(story_id, status).unwrap();
+        }
+
+        assert_eq!(
+            db.status_counts().unwrap(),
+            vec![
+                (Status::Open, 1),
+                (Status::InProgress, 3),
+                (Status::Resolved, 0),
+                (Status::Closed, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn stories_by_status_should_span_epics() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let first_epic = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let second_epic = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let mut story_ids = Vec::new();
+        for (epic_id, status) in [
+            (first_epic, Status::InProgress),
+            (first_epic, Status::Open),
+            (second_epic, Status::Closed),
+            (second_epic, Status::InProgress),
+        ] {
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.update_story_status(story_id, status).unwrap();
+            story_ids.push(story_id);
+        }
+
+        assert_eq!(
+            db.stories_by_status(Status::InProgress).unwrap(),
+            vec![(first_epic, story_ids[0]), (second_epic, story_ids[3])]
+        );
+        assert_eq!(
+            db.stories_by_status(Status::Closed).unwrap(),
+            vec![(second_epic, story_ids[2])]
+        );
+        assert!(db.stories_by_status(Status::Resolved).unwrap().is_empty());
+    }
 
     #[test]
     fn create_epic_should_work() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
         let epic = Epic::new("".to_owned(), "".to_owned());
 
         // TODO: fix this error by deriving the appropriate traits for Epic
@@ -215,24 +3656,64 @@ mod tests {
         assert_eq!(db_state.epics.get(&id), Some(&epic));
     }
 
+    #[test]
+    fn create_epic_returning_should_return_stored_epic() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic = Epic::new("name".to_owned(), "description".to_owned());
+
+        let (id, returned) = db.create_epic_returning(epic).unwrap();
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(db_state.epics.get(&id), Some(&returned));
+    }
+
+    #[test]
+    fn create_story_returning_should_return_stored_story() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story = Story::new("name".to_owned(), "description".to_owned());
+
+        let (id, returned) = db.create_story_returning(story, epic_id).unwrap();
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(db_state.stories.get(&id), Some(&returned));
+        assert!(db
+            .create_story_returning(Story::new("".to_owned(), "".to_owned()), 999)
+            .is_err());
+    }
+
+    #[test]
+    fn create_should_error_when_item_ids_are_exhausted() {
+        let mock = MockDB::new();
+        let mut state = mock.read_db().unwrap();
+        state.last_item_id = u32::MAX;
+        mock.write_db(&state).unwrap();
+
+        let db = JiraDatabase::with_database(Box::new(mock));
+
+        assert!(matches!(
+            db.create_epic(Epic::new("".to_owned(), "".to_owned())),
+            Err(JiraError::IdsExhausted(u32::MAX))
+        ));
+        assert_eq!(db.read_db().unwrap(), state);
+    }
+
     #[test]
     fn create_story_should_error_if_invalid_epic_id() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
         let story = Story::new("".to_owned(), "".to_owned());
 
         let non_existent_epic_id = 999;
 
         let result = db.create_story(story, non_existent_epic_id);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(JiraError::EpicNotFound(999))));
     }
 
     #[test]
     fn create_story_should_work() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
         let epic = Epic::new("".to_owned(), "".to_owned());
         let story = Story::new("".to_owned(), "".to_owned());
 
@@ -256,23 +3737,81 @@ mod tests {
         assert_eq!(db_state.stories.get(&id), Some(&story));
     }
 
+    #[test]
+    fn strict_delete_should_only_delete_closed_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new())).with_strict_delete(true);
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(matches!(
+            db.delete_story(epic_id, story_id),
+            Err(JiraError::InvalidOperation(_))
+        ));
+        assert!(db.read_db().unwrap().stories.contains_key(&story_id));
+
+        db.update_story_status(story_id, Status::Closed).unwrap();
+        db.delete_story(epic_id, story_id).unwrap();
+        assert!(!db.read_db().unwrap().stories.contains_key(&story_id));
+    }
+
+    #[test]
+    fn strict_delete_should_keep_open_epics_when_keeping_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new())).with_strict_delete(true);
+        let source = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let dest = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert!(matches!(
+            db.delete_epic_keep_stories(source, None),
+            Err(JiraError::InvalidOperation(_))
+        ));
+        assert!(db.read_db().unwrap().epics.contains_key(&source));
+
+        db.merge_epics(source, dest).unwrap();
+        assert!(!db.read_db().unwrap().epics.contains_key(&source));
+    }
+
+    #[test]
+    fn strict_delete_should_only_delete_epic_changes() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new())).with_strict_delete(true);
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_epic_status(epic_id, Status::Closed).unwrap();
+
+        assert!(matches!(
+            db.delete_epic(epic_id),
+            Err(JiraError::InvalidOperation(_))
+        ));
+
+        db.update_story_status(story_id, Status::Closed).unwrap();
+        db.delete_epic(epic_id).unwrap();
+        assert!(db.read_db().unwrap().epics.is_empty());
+    }
+
     #[test]
     fn delete_epic_should_error_if_invalid_epic_id() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
 
         let non_existent_epic_id = 999;
 
         let result = db.delete_epic(non_existent_epic_id);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(JiraError::EpicNotFound(999))));
     }
 
     #[test]
     fn delete_epic_should_work() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
         let epic = Epic::new("".to_owned(), "".to_owned());
         let story = Story::new("".to_owned(), "".to_owned());
 
@@ -298,11 +3837,129 @@ mod tests {
         assert_eq!(db_state.stories.get(&story_id), None);
     }
 
+    #[test]
+    fn merge_epics_should_move_stories_and_remove_source() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let source = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let dest = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let dest_story = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), dest)
+            .unwrap();
+        let source_story = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), source)
+            .unwrap();
+
+        db.merge_epics(source, dest).unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert!(!db_state.epics.contains_key(&source));
+        assert_eq!(
+            db_state.epics[&dest].stories,
+            vec![dest_story, source_story]
+        );
+        assert_eq!(db_state.stories.len(), 2);
+    }
+
+    #[test]
+    fn merge_epics_should_validate_ids() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert!(matches!(
+            db.merge_epics(epic_id, epic_id),
+            Err(JiraError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            db.merge_epics(epic_id, 999),
+            Err(JiraError::EpicNotFound(999))
+        ));
+        assert!(matches!(
+            db.merge_epics(999, epic_id),
+            Err(JiraError::EpicNotFound(999))
+        ));
+        assert!(db.read_db().unwrap().epics.contains_key(&epic_id));
+    }
+
+    #[test]
+    fn delete_epic_keep_stories_should_error_if_invalid_epic_id() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+
+        let result = db.delete_epic_keep_stories(999, None);
+        assert!(matches!(result, Err(JiraError::EpicNotFound(999))));
+    }
+
+    #[test]
+    fn delete_epic_keep_stories_should_error_if_invalid_reassign_target() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert!(matches!(
+            db.delete_epic_keep_stories(epic_id, Some(999)),
+            Err(JiraError::EpicNotFound(999))
+        ));
+        assert!(matches!(
+            db.delete_epic_keep_stories(epic_id, Some(epic_id)),
+            Err(JiraError::InvalidOperation(_))
+        ));
+        assert!(db.read_db().unwrap().epics.contains_key(&epic_id));
+    }
+
+    #[test]
+    fn delete_epic_keep_stories_should_orphan_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let story = Story::new("".to_owned(), "".to_owned());
+
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db.create_story(story.clone(), epic_id).unwrap();
+
+        let result = db.delete_epic_keep_stories(epic_id, None);
+        assert!(result.is_ok());
+
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(db_state.epics.get(&epic_id), None);
+        assert_eq!(db_state.stories.get(&story_id), Some(&story));
+    }
+
+    #[test]
+    fn delete_epic_keep_stories_should_reassign_stories() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let story = Story::new("".to_owned(), "".to_owned());
+
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let target_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db.create_story(story.clone(), epic_id).unwrap();
+
+        let result = db.delete_epic_keep_stories(epic_id, Some(target_id));
+        assert!(result.is_ok());
+
+        let db_state = db.read_db().unwrap();
+
+        assert_eq!(db_state.epics.get(&epic_id), None);
+        assert_eq!(
+            db_state.epics.get(&target_id).unwrap().stories,
+            vec![story_id]
+        );
+        assert_eq!(db_state.stories.get(&story_id), Some(&story));
+    }
+
     #[test]
     fn delete_story_should_error_if_invalid_epic_id() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
         let epic = Epic::new("".to_owned(), "".to_owned());
         let story = Story::new("".to_owned(), "".to_owned());
 
@@ -319,14 +3976,12 @@ mod tests {
         let non_existent_epic_id = 999;
 
         let result = db.delete_story(non_existent_epic_id, story_id);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(JiraError::EpicNotFound(999))));
     }
 
     #[test]
     fn delete_story_should_error_if_story_not_found_in_epic() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
         let epic = Epic::new("".to_owned(), "".to_owned());
         let story = Story::new("".to_owned(), "".to_owned());
 
@@ -341,14 +3996,36 @@ mod tests {
         let non_existent_story_id = 999;
 
         let result = db.delete_story(epic_id, non_existent_story_id);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(JiraError::StoryNotFound(999))));
+    }
+
+    #[test]
+    fn delete_story_should_error_if_story_belongs_to_another_epic() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let other_epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let result = db.delete_story(other_epic_id, story_id);
+        assert!(matches!(result, Err(JiraError::InvalidOperation(_))));
+
+        let db_state = db.read_db().unwrap();
+        assert!(db_state.stories.contains_key(&story_id));
+        assert_eq!(
+            db_state.epics.get(&epic_id).unwrap().stories,
+            vec![story_id]
+        );
     }
 
     #[test]
     fn delete_story_should_work() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
         let epic = Epic::new("".to_owned(), "".to_owned());
         let story = Story::new("".to_owned(), "".to_owned());
 
@@ -381,21 +4058,212 @@ mod tests {
 
     #[test]
     fn update_epic_status_should_error_if_invalid_epic_id() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
 
         let non_existent_epic_id = 999;
 
         let result = db.update_epic_status(non_existent_epic_id, Status::Closed);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(JiraError::EpicNotFound(999))));
+    }
+
+    #[test]
+    fn update_epic_status_should_run_hook_only_when_epic_closes() {
+        let closed = Arc::new(Mutex::new(vec![]));
+        let recorded = Arc::clone(&closed);
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()))
+            .with_epic_closed_hook(move |epic| recorded.lock().unwrap().push(epic.name.clone()));
+        let epic_id = db
+            .create_epic(Epic::new("release".to_owned(), "".to_owned()))
+            .unwrap();
+
+        db.update_epic_status(epic_id, Status::InProgress).unwrap();
+        db.update_epic_status(epic_id, Status::Closed).unwrap();
+        db.update_epic_status(epic_id, Status::Closed).unwrap();
+        db.update_epic_status(epic_id, Status::Resolved).unwrap();
+
+        assert_eq!(*closed.lock().unwrap(), vec!["release".to_owned()]);
+    }
+
+    #[test]
+    fn toggle_epic_star_should_flip_starred() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert!(db.toggle_epic_star(epic_id).unwrap());
+        assert!(db.read_db().unwrap().epics[&epic_id].starred);
+        assert!(!db.toggle_epic_star(epic_id).unwrap());
+        assert!(matches!(
+            db.toggle_epic_star(999),
+            Err(JiraError::EpicNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn toggle_story_pin_should_flip_pinned() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(db.toggle_story_pin(story_id).unwrap());
+        assert!(db.read_db().unwrap().stories[&story_id].pinned);
+        assert!(!db.toggle_story_pin(story_id).unwrap());
+        assert!(matches!(
+            db.toggle_story_pin(999),
+            Err(JiraError::StoryNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn stories_assigned_to_should_match_any_assignee() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_ids = (0..3)
+            .map(|_| {
+                db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap()
+            })
+            .collect_vec();
+
+        assert!(db.add_assignee(story_ids[0], "alice").unwrap());
+        assert!(db.add_assignee(story_ids[1], "bob").unwrap());
+        assert!(db.add_assignee(story_ids[1], "Alice").unwrap());
+        assert!(!db.add_assignee(story_ids[1], "ALICE").unwrap());
+        assert!(db.add_assignee(story_ids[2], "bob").unwrap());
+
+        assert_eq!(
+            db.stories_assigned_to("alice").unwrap(),
+            vec![story_ids[0], story_ids[1]]
+        );
+        assert!(db.remove_assignee(story_ids[1], "alice").unwrap());
+        assert!(!db.remove_assignee(story_ids[1], "alice").unwrap());
+        assert_eq!(db.stories_assigned_to("alice").unwrap(), vec![story_ids[0]]);
+        assert!(matches!(
+            db.add_assignee(999, "alice"),
+            Err(JiraError::StoryNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn time_rollup_should_count_untracked_stories_as_zero() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_ids = (0..3)
+            .map(|_| {
+                db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap()
+            })
+            .collect_vec();
+
+        db.track_time(story_ids[0], Some(3.0), Some(4.5)).unwrap();
+        db.track_time(story_ids[1], Some(2.0), None).unwrap();
+        db.track_time(story_ids[1], None, Some(1.0)).unwrap();
+
+        assert_eq!(db.time_rollup(epic_id).unwrap(), (5.0, 5.5));
+        let story = &db.read_db().unwrap().stories[&story_ids[1]];
+        assert_eq!(
+            (story.estimate_hours, story.actual_hours),
+            (Some(2.0), Some(1.0))
+        );
+        assert!(matches!(
+            db.track_time(story_ids[2], Some(-1.0), None),
+            Err(JiraError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            db.time_rollup(999),
+            Err(JiraError::EpicNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn critical_path_should_pick_the_longest_story_without_dependencies() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        assert!(db.critical_path(epic_id).unwrap().is_empty());
+
+        let story_ids = (0..4)
+            .map(|_| {
+                db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap()
+            })
+            .collect_vec();
+        db.track_time(story_ids[0], Some(2.0), None).unwrap();
+        db.track_time(story_ids[1], Some(5.0), None).unwrap();
+        db.track_time(story_ids[3], Some(5.0), None).unwrap();
+
+        assert_eq!(db.critical_path(epic_id).unwrap(), vec![story_ids[1]]);
+    }
+
+    #[test]
+    fn edit_epic_should_only_change_given_fields() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("name".to_owned(), "description".to_owned()))
+            .unwrap();
+
+        db.edit_epic(
+            epic_id,
+            ItemEdit {
+                description: Some("new description".to_owned()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let epic = &db.read_db().unwrap().epics[&epic_id];
+        assert_eq!(epic.name, "name");
+        assert_eq!(epic.description, "new description");
+        assert_eq!(epic.status, Status::Open);
+    }
+
+    #[test]
+    fn edit_story_should_only_change_given_fields() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(
+                Story::new("name".to_owned(), "description".to_owned()),
+                epic_id,
+            )
+            .unwrap();
+
+        db.edit_story(
+            story_id,
+            ItemEdit {
+                name: Some("new name".to_owned()),
+                status: Some(Status::InProgress),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let story = &db.read_db().unwrap().stories[&story_id];
+        assert_eq!(story.name, "new name");
+        assert_eq!(story.description, "description");
+        assert_eq!(story.status, Status::InProgress);
+        assert!(matches!(
+            db.edit_story(999, ItemEdit::default()),
+            Err(JiraError::StoryNotFound(999))
+        ));
     }
 
     #[test]
     fn update_epic_status_should_work() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
         let epic = Epic::new("".to_owned(), "".to_owned());
 
         let result = db.create_epic(epic);
@@ -415,21 +4283,17 @@ mod tests {
 
     #[test]
     fn update_story_status_should_error_if_invalid_story_id() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
 
         let non_existent_story_id = 999;
 
         let result = db.update_story_status(non_existent_story_id, Status::Closed);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(JiraError::StoryNotFound(999))));
     }
 
     #[test]
     fn update_story_status_should_work() {
-        let db = JiraDatabase {
-            database: Box::new(MockDB::new()),
-        };
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
         let epic = Epic::new("".to_owned(), "".to_owned());
         let story = Story::new("".to_owned(), "".to_owned());
 
@@ -453,6 +4317,202 @@ mod tests {
         );
     }
 
+    #[test]
+    fn db_schema_should_describe_epics_and_stories() {
+        let schema = db_schema().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&schema).unwrap();
+
+        let properties = value.get("properties").unwrap();
+        assert!(properties.get("epics").is_some());
+        assert!(properties.get("stories").is_some());
+    }
+
+    #[test]
+    fn add_subtask_should_error_if_invalid_story_id() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+
+        let result = db.add_subtask(999, "subtask".to_owned());
+        assert!(matches!(result, Err(JiraError::StoryNotFound(999))));
+    }
+
+    #[test]
+    fn add_and_toggle_subtask_should_work() {
+        let db = JiraDatabase::with_database(Box::new(MockDB::new()));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        assert!(db.add_subtask(story_id, "subtask".to_owned()).is_ok());
+        assert!(db.toggle_subtask(story_id, 0).is_ok());
+        assert!(matches!(
+            db.toggle_subtask(story_id, 1),
+            Err(JiraError::SubtaskNotFound { number: 2, .. })
+        ));
+
+        let db_state = db.read_db().unwrap();
+        let story = db_state.stories.get(&story_id).unwrap();
+
+        assert_eq!(story.subtasks.len(), 1);
+        assert_eq!(story.completed_subtasks(), 1);
+    }
+
+    mod read_only_database {
+        use super::*;
+
+        struct ReadOnlyDB {
+            inner: MockDB,
+        }
+
+        impl Database for ReadOnlyDB {
+            fn read_db(&self) -> Result<DBState> {
+                self.inner.read_db()
+            }
+
+            fn write_db(&self, _db_state: &DBState) -> Result<()> {
+                Err(std::io::Error::from(ErrorKind::PermissionDenied).into())
+            }
+
+            fn location(&self) -> Option<String> {
+                Some("/usr/share/jirrah/db.json".to_owned())
+            }
+        }
+
+        #[test]
+        fn writes_should_report_unwritable_data_directory() {
+            let db = JiraDatabase::with_database(Box::new(ReadOnlyDB {
+                inner: MockDB::new(),
+            }));
+
+            let error = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap_err();
+
+            assert!(matches!(&error, JiraError::NotWritable(path) if path == "/usr/share/jirrah"));
+            assert_eq!(
+                error.to_string(),
+                "data directory is not writable: /usr/share/jirrah"
+            );
+        }
+
+        #[test]
+        fn flush_should_report_unwritable_data_directory() {
+            let db = JiraDatabase::with_database(Box::new(ReadOnlyDB {
+                inner: MockDB::new(),
+            }))
+            .batched();
+            db.create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+
+            assert!(matches!(db.flush(), Err(JiraError::NotWritable(_))));
+        }
+    }
+
+    mod shared_database {
+        use std::thread;
+
+        use super::*;
+
+        #[test]
+        fn readers_and_writer_should_share_the_database() {
+            let db = Arc::new(SharedJiraDatabase::new(JiraDatabase::with_database(
+                Box::new(MockDB::new()),
+            )));
+
+            let writer = {
+                let db = Arc::clone(&db);
+                thread::spawn(move || {
+                    for _ in 0..20 {
+                        db.create_epic(Epic::new("".to_owned(), "".to_owned()))
+                            .unwrap();
+                    }
+                })
+            };
+            let readers = (0..4)
+                .map(|_| {
+                    let db = Arc::clone(&db);
+                    thread::spawn(move || {
+                        let mut seen = 0;
+                        for _ in 0..50 {
+                            let count = db.stats().unwrap().epic_count;
+                            assert!(count >= seen && count <= 20);
+                            seen = count;
+                        }
+                    })
+                })
+                .collect_vec();
+
+            writer.join().unwrap();
+            for reader in readers {
+                reader.join().unwrap();
+            }
+
+            let db_state = db.read_db().unwrap();
+            assert_eq!(db_state.epics.len(), 20);
+            assert_eq!(db_state.last_item_id, 20);
+        }
+    }
+
+    mod batched_database {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use super::*;
+
+        struct CountingDB {
+            inner: MockDB,
+            writes: Arc<AtomicUsize>,
+        }
+
+        impl Database for CountingDB {
+            fn read_db(&self) -> Result<DBState> {
+                self.inner.read_db()
+            }
+
+            fn write_db(&self, db_state: &DBState) -> Result<()> {
+                self.writes.fetch_add(1, Ordering::SeqCst);
+                self.inner.write_db(db_state)
+            }
+        }
+
+        fn counting_db() -> (JiraDatabase, Arc<AtomicUsize>) {
+            let writes = Arc::new(AtomicUsize::new(0));
+            let database = CountingDB {
+                inner: MockDB::new(),
+                writes: Arc::clone(&writes),
+            };
+
+            (
+                JiraDatabase::with_database(Box::new(database)).batched(),
+                writes,
+            )
+        }
+
+        #[test]
+        fn mutations_should_be_written_once_on_flush() {
+            let (db, writes) = counting_db();
+
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            for _ in 0..10 {
+                db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                    .unwrap();
+            }
+
+            assert_eq!(writes.load(Ordering::SeqCst), 0);
+            assert_eq!(db.read_db().unwrap().stories.len(), 10);
+
+            db.flush().unwrap();
+            assert_eq!(writes.load(Ordering::SeqCst), 1);
+
+            db.flush().unwrap();
+            assert_eq!(writes.load(Ordering::SeqCst), 1);
+            assert_eq!(db.read_db().unwrap().stories.len(), 10);
+        }
+    }
+
     mod database {
         use std::collections::HashMap;
         use std::io::Write;
@@ -463,8 +4523,63 @@ mod tests {
         fn read_db_should_fail_with_invalid_path() {
             let db = JSONFileDatabase {
                 file_path: "INVALID_PATH".to_owned(),
+                compact: false,
             };
-            assert!(db.read_db().is_err());
+            assert!(matches!(db.read_db(), Err(JiraError::IoError(_))));
+        }
+
+        #[test]
+        fn init_json_file_should_create_missing_board() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("db.json");
+
+            let db = JiraDatabase::init_json_file(path.to_str().unwrap(), false).unwrap();
+
+            assert_eq!(db.read_db().unwrap(), DBState::default());
+
+            let nested = dir.path().join("boards").join("db.json");
+            let db = JiraDatabase::init_json_file(nested.to_str().unwrap(), false).unwrap();
+
+            assert_eq!(db.read_db().unwrap(), DBState::default());
+        }
+
+        #[test]
+        fn open_json_file_should_not_create_a_missing_board() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("boards").join("db.json");
+
+            assert!(matches!(
+                JiraDatabase::open_json_file(path.to_str().unwrap(), false),
+                Err(JiraError::IoError(_))
+            ));
+            assert!(matches!(
+                JiraDatabase::open_delta_file(path.to_str().unwrap(), false),
+                Err(JiraError::IoError(_))
+            ));
+            assert!(!dir.path().join("boards").exists());
+        }
+
+        #[test]
+        fn read_only_should_reject_writes_and_leave_the_file_untouched() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("db.json");
+            let path = path.to_str().unwrap();
+            JiraDatabase::init_json_file(path, false)
+                .unwrap()
+                .create_epic(Epic::new("Launch".to_owned(), "".to_owned()))
+                .unwrap();
+            let before = std::fs::read_to_string(path).unwrap();
+
+            let db = JiraDatabase::init_json_file(path, false)
+                .unwrap()
+                .read_only();
+
+            assert!(matches!(
+                db.create_epic(Epic::new("".to_owned(), "".to_owned())),
+                Err(JiraError::ReadOnly)
+            ));
+            assert_eq!(db.read_db().unwrap().epics.len(), 1);
+            assert_eq!(std::fs::read_to_string(path).unwrap(), before);
         }
 
         #[test]
@@ -480,11 +4595,12 @@ mod tests {
                     .to_str()
                     .expect("failed to convert tmpfile path to str")
                     .to_string(),
+                compact: false,
             };
 
             let result = db.read_db();
 
-            assert!(result.is_err());
+            assert!(matches!(result, Err(JiraError::SerdeError(_))));
         }
 
         #[test]
@@ -500,6 +4616,7 @@ mod tests {
                     .to_str()
                     .expect("failed to convert tmpfile path to str")
                     .to_string(),
+                compact: false,
             };
 
             let result = db.read_db();
@@ -520,18 +4637,34 @@ mod tests {
                     .to_str()
                     .expect("failed to convert tmpfile path to str")
                     .to_string(),
+                compact: false,
             };
 
             let story = Story {
                 name: "epic 1".to_owned(),
                 description: "epic 1".to_owned(),
                 status: Status::Open,
+                subtasks: vec![],
+                status_history: vec![],
+                reopen_reasons: vec![],
+                updated_at: None,
+                snoozed_until: None,
+                due_date: None,
+                slug: "epic-1".to_owned(),
+                pinned: false,
+                estimate_hours: Some(2.5),
+                actual_hours: None,
+                percent_complete: 0,
+                assignees: vec![],
             };
             let epic = Epic {
                 name: "epic 1".to_owned(),
                 description: "epic 1".to_owned(),
                 status: Status::Open,
                 stories: vec![2],
+                starred: false,
+                slug: "epic-1".to_owned(),
+                dod: vec![],
             };
 
             let mut stories = HashMap::new();
@@ -544,6 +4677,8 @@ mod tests {
                 last_item_id: 2,
                 epics,
                 stories,
+                project_name: Some("jirrah".to_owned()),
+                project_version: Some("0.2".to_owned()),
             };
 
             let write_result = db.write_db(&state);
@@ -552,5 +4687,133 @@ mod tests {
             assert!(write_result.is_ok());
             assert_eq!(read_result, state);
         }
+
+        #[test]
+        fn write_db_should_write_the_same_state_byte_for_byte() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = |name: &str| dir.path().join(name).to_str().unwrap().to_owned();
+            let db = JiraDatabase::init_json_file(path("first.json"), false).unwrap();
+            for id in 0..12 {
+                let epic_id = db
+                    .create_epic(Epic::new(format!("epic {}", id), "".to_owned()))
+                    .unwrap();
+                db.create_story(Story::new(format!("story {}", id), "".to_owned()), epic_id)
+                    .unwrap();
+            }
+            let state = db.read_db().unwrap();
+
+            let copy = JSONFileDatabase {
+                file_path: path("second.json"),
+                compact: false,
+            };
+            copy.write_db(&state.clone()).unwrap();
+            copy.write_db(&copy.read_db().unwrap()).unwrap();
+
+            let first = std::fs::read_to_string(path("first.json")).unwrap();
+            assert_eq!(std::fs::read_to_string(path("second.json")).unwrap(), first);
+            assert!(first.find("\"2\"").unwrap() < first.find("\"10\"").unwrap());
+        }
+
+        #[test]
+        fn write_db_should_support_compact_output() {
+            let tmpfile = tempfile::NamedTempFile::new().unwrap();
+
+            let db = JSONFileDatabase {
+                file_path: tmpfile
+                    .path()
+                    .to_str()
+                    .expect("failed to convert tmpfile path to str")
+                    .to_string(),
+                compact: true,
+            };
+
+            let mut epic = Epic::new("epic 1".to_owned(), "epic 1".to_owned());
+            epic.stories.push(2);
+
+            let mut epics = HashMap::new();
+            epics.insert(1, epic);
+
+            let mut stories = HashMap::new();
+            stories.insert(2, Story::new("story 1".to_owned(), "story 1".to_owned()));
+
+            let state = DBState {
+                last_item_id: 2,
+                epics,
+                stories,
+                project_name: None,
+                project_version: None,
+            };
+
+            db.write_db(&state).unwrap();
+
+            let content = std::fs::read_to_string(tmpfile.path()).unwrap();
+            assert!(!content.contains('\n'));
+            assert_eq!(db.read_db().unwrap(), state);
+        }
+    }
+
+    mod delta_database {
+        use super::*;
+
+        fn delta_db(dir: &tempfile::TempDir, compaction_threshold: usize) -> DeltaDatabase {
+            let path = dir.path().join("db.json");
+            let snapshot = JSONFileDatabase {
+                file_path: path.to_str().unwrap().to_owned(),
+                compact: false,
+            };
+            if !path.exists() {
+                snapshot.write_db(&DBState::default()).unwrap();
+            }
+
+            DeltaDatabase {
+                compaction_threshold,
+                ..DeltaDatabase::new(snapshot)
+            }
+        }
+
+        fn edit_board(db: &JiraDatabase) {
+            let epic_id = db
+                .create_epic(Epic::new("Launch".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("Docs".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            let other_story_id = db
+                .create_story(Story::new("Tests".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.update_story_status(story_id, Status::InProgress)
+                .unwrap();
+            db.delete_story(epic_id, other_story_id).unwrap();
+            db.update_epic_status(epic_id, Status::Resolved).unwrap();
+        }
+
+        #[test]
+        fn read_db_should_replay_log_over_snapshot() {
+            let dir = tempfile::tempdir().unwrap();
+            let db = JiraDatabase::with_database(Box::new(delta_db(&dir, usize::MAX)));
+            edit_board(&db);
+
+            let reopened = delta_db(&dir, usize::MAX);
+
+            assert_eq!(reopened.snapshot.read_db().unwrap(), DBState::default());
+            assert_eq!(reopened.read_db().unwrap(), db.read_db().unwrap());
+        }
+
+        #[test]
+        fn compaction_should_produce_equivalent_state() {
+            let dir = tempfile::tempdir().unwrap();
+            let db = JiraDatabase::with_database(Box::new(delta_db(&dir, 3)));
+            edit_board(&db);
+            let expected = db.read_db().unwrap();
+
+            let reopened = delta_db(&dir, 3);
+            let (replayed, entries) = reopened.replay().unwrap();
+            assert!(entries < 3);
+            assert_eq!(replayed, expected);
+
+            reopened.compact(&replayed).unwrap();
+            assert_eq!(reopened.snapshot.read_db().unwrap(), expected);
+            assert_eq!(delta_db(&dir, 3).replay().unwrap(), (expected, 0));
+        }
     }
 }
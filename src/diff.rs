@@ -0,0 +1,207 @@
+use std::{collections::HashMap, fmt::Display};
+
+use itertools::Itertools;
+
+use crate::models::{DBState, ItemRef, Sortable, Status};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ItemChange {
+    Added {
+        item: ItemRef,
+        name: String,
+    },
+    Removed {
+        item: ItemRef,
+        name: String,
+    },
+    Renamed {
+        item: ItemRef,
+        from: String,
+        to: String,
+    },
+    StatusChanged {
+        item: ItemRef,
+        from: Status,
+        to: Status,
+    },
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BoardDiff {
+    pub epics: Vec<ItemChange>,
+    pub stories: Vec<ItemChange>,
+}
+
+impl BoardDiff {
+    pub fn is_empty(&self) -> bool {
+        self.epics.is_empty() && self.stories.is_empty()
+    }
+}
+
+pub fn diff_states(old: &DBState, new: &DBState) -> BoardDiff {
+    BoardDiff {
+        epics: diff_items(&old.epics, &new.epics, ItemRef::Epic),
+        stories: diff_items(&old.stories, &new.stories, ItemRef::Story),
+    }
+}
+
+fn diff_items<T: Sortable>(
+    old: &HashMap<u32, T>,
+    new: &HashMap<u32, T>,
+    item_ref: fn(u32) -> ItemRef,
+) -> Vec<ItemChange> {
+    let mut changes = vec![];
+
+    for id in old.keys().chain(new.keys()).unique().sorted() {
+        let item = item_ref(*id);
+        match (old.get(id), new.get(id)) {
+            (None, Some(added)) => changes.push(ItemChange::Added {
+                item,
+                name: added.sort_name().to_owned(),
+            }),
+            (Some(removed), None) => changes.push(ItemChange::Removed {
+                item,
+                name: removed.sort_name().to_owned(),
+            }),
+            (Some(before), Some(after)) => {
+                if before.sort_name() != after.sort_name() {
+                    changes.push(ItemChange::Renamed {
+                        item,
+                        from: before.sort_name().to_owned(),
+                        to: after.sort_name().to_owned(),
+                    });
+                }
+                if before.sort_status() != after.sort_status() {
+                    changes.push(ItemChange::StatusChanged {
+                        item,
+                        from: before.sort_status().clone(),
+                        to: after.sort_status().clone(),
+                    });
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    changes
+}
+
+impl Display for ItemChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemChange::Added { item, name } => write!(f, "+ {} {}", item, name),
+            ItemChange::Removed { item, name } => write!(f, "- {} {}", item, name),
+            ItemChange::Renamed { item, from, to } => {
+                write!(f, "~ {} renamed: {} -> {}", item, from, to)
+            }
+            ItemChange::StatusChanged { item, from, to } => {
+                write!(f, "~ {} status: {} -> {}", item, from, to)
+            }
+        }
+    }
+}
+
+impl Display for BoardDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+
+        for (title, changes) in [("EPICS", &self.epics), ("STORIES", &self.stories)] {
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(f, "{}", title)?;
+            for change in changes {
+                writeln!(f, "{}", change)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Epic, Story};
+
+    fn board() -> DBState {
+        let mut epic = Epic::new("Epic".to_owned(), "".to_owned());
+        epic.stories = vec![2, 3];
+
+        DBState {
+            last_item_id: 3,
+            epics: HashMap::from([(1, epic)]),
+            stories: HashMap::from([
+                (2, Story::new("First".to_owned(), "".to_owned())),
+                (3, Story::new("Second".to_owned(), "".to_owned())),
+            ]),
+            project_name: None,
+            project_version: None,
+        }
+    }
+
+    #[test]
+    fn diff_states_should_be_empty_for_identical_boards() {
+        assert!(diff_states(&board(), &board()).is_empty());
+    }
+
+    #[test]
+    fn diff_states_should_report_added_epic() {
+        let old = board();
+        let mut new = board();
+        new.epics
+            .insert(4, Epic::new("New epic".to_owned(), "".to_owned()));
+
+        let diff = diff_states(&old, &new);
+
+        assert_eq!(
+            diff.epics,
+            vec![ItemChange::Added {
+                item: ItemRef::Epic(4),
+                name: "New epic".to_owned(),
+            }]
+        );
+        assert!(diff.stories.is_empty());
+    }
+
+    #[test]
+    fn diff_states_should_report_removed_story() {
+        let old = board();
+        let mut new = board();
+        new.stories.remove(&3);
+
+        let diff = diff_states(&old, &new);
+
+        assert_eq!(
+            diff.stories,
+            vec![ItemChange::Removed {
+                item: ItemRef::Story(3),
+                name: "Second".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_states_should_report_status_change() {
+        let old = board();
+        let mut new = board();
+        new.stories.get_mut(&2).unwrap().status = Status::Closed;
+
+        let diff = diff_states(&old, &new);
+
+        assert_eq!(
+            diff.stories,
+            vec![ItemChange::StatusChanged {
+                item: ItemRef::Story(2),
+                from: Status::Open,
+                to: Status::Closed,
+            }]
+        );
+        assert_eq!(
+            diff.to_string(),
+            "STORIES\n~ STORY-2 status: OPEN -> Closed\n"
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::{collections::HashSet, fmt::Display};
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::models::DBState;
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum Issue {
+    DanglingStory { epic_id: u32, story_id: u32 },
+    DuplicateStory { epic_id: u32, story_id: u32 },
+    StaleLastItemId { last_item_id: u32, max_id: u32 },
+    OrphanedStory { story_id: u32 },
+}
+
+impl Issue {
+    pub fn fixable(&self) -> bool {
+        !matches!(self, Issue::OrphanedStory { .. })
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::DanglingStory { epic_id, story_id } => write!(
+                f,
+                "epic {} lists story {}, which does not exist",
+                epic_id, story_id
+            ),
+            Issue::DuplicateStory { epic_id, story_id } => write!(
+                f,
+                "epic {} lists story {} more than once",
+                epic_id, story_id
+            ),
+            Issue::StaleLastItemId {
+                last_item_id,
+                max_id,
+            } => write!(
+                f,
+                "last_item_id is {} but id {} is already in use",
+                last_item_id, max_id
+            ),
+            Issue::OrphanedStory { story_id } => {
+                write!(f, "story {} does not belong to any epic", story_id)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub issues: Vec<Issue>,
+    pub fixed: Vec<Issue>,
+}
+
+impl Report {
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+pub fn check_references(db: &DBState) -> Vec<Issue> {
+    db.epics
+        .iter()
+        .sorted_by_key(|(epic_id, _)| **epic_id)
+        .flat_map(|(epic_id, epic)| {
+            epic.stories
+                .iter()
+                .filter(|story_id| !db.stories.contains_key(story_id))
+                .map(|story_id| Issue::DanglingStory {
+                    epic_id: *epic_id,
+                    story_id: *story_id,
+                })
+        })
+        .collect()
+}
+
+pub fn check_duplicates(db: &DBState) -> Vec<Issue> {
+    db.epics
+        .iter()
+        .sorted_by_key(|(epic_id, _)| **epic_id)
+        .flat_map(|(epic_id, epic)| {
+            epic.stories
+                .iter()
+                .duplicates()
+                .map(|story_id| Issue::DuplicateStory {
+                    epic_id: *epic_id,
+                    story_id: *story_id,
+                })
+        })
+        .collect()
+}
+
+pub fn check_last_item_id(db: &DBState) -> Vec<Issue> {
+    let max_id = db
+        .epics
+        .keys()
+        .chain(db.stories.keys())
+        .copied()
+        .max()
+        .unwrap_or(0);
+
+    match db.last_item_id < max_id {
+        true => vec![Issue::StaleLastItemId {
+            last_item_id: db.last_item_id,
+            max_id,
+        }],
+        false => vec![],
+    }
+}
+
+pub fn check_orphans(db: &DBState) -> Vec<Issue> {
+    let owned: HashSet<_> = db.epics.values().flat_map(|epic| &epic.stories).collect();
+
+    db.stories
+        .keys()
+        .filter(|story_id| !owned.contains(story_id))
+        .sorted()
+        .map(|story_id| Issue::OrphanedStory {
+            story_id: *story_id,
+        })
+        .collect()
+}
+
+pub fn run_checks(db: &DBState) -> Vec<Issue> {
+    [
+        check_references,
+        check_duplicates,
+        check_last_item_id,
+        check_orphans,
+    ]
+    .iter()
+    .flat_map(|check| check(db))
+    .collect()
+}
+
+pub fn fix_issue(db: &mut DBState, issue: &Issue) -> bool {
+    match issue {
+        Issue::DanglingStory { epic_id, story_id } => match db.epics.get_mut(epic_id) {
+            Some(epic) => {
+                epic.stories.retain(|id| id != story_id);
+                true
+            }
+            None => false,
+        },
+        Issue::DuplicateStory { epic_id, story_id } => match db.epics.get_mut(epic_id) {
+            Some(epic) => {
+                let mut seen = false;
+                epic.stories.retain(|id| {
+                    let duplicate = id == story_id && seen;
+                    seen |= id == story_id;
+                    !duplicate
+                });
+                true
+            }
+            None => false,
+        },
+        Issue::StaleLastItemId { max_id, .. } => {
+            db.last_item_id = db.last_item_id.max(*max_id);
+            true
+        }
+        Issue::OrphanedStory { .. } => false,
+    }
+}
+
+pub fn diagnose(db: &mut DBState, fix: bool) -> Report {
+    let (fixed, issues) = run_checks(db)
+        .into_iter()
+        .partition(|issue| fix && issue.fixable() && fix_issue(db, issue));
+
+    Report { issues, fixed }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Epic, Story};
+
+    fn board() -> DBState {
+        let mut db = DBState {
+            last_item_id: 2,
+            ..Default::default()
+        };
+        let mut epic = Epic::new("".to_owned(), "".to_owned());
+        epic.stories = vec![2];
+        db.epics.insert(1, epic);
+        db.stories
+            .insert(2, Story::new("".to_owned(), "".to_owned()));
+        db
+    }
+
+    #[test]
+    fn diagnose_should_report_nothing_for_a_clean_board() {
+        let mut db = board();
+        let before = db.clone();
+
+        assert!(run_checks(&db).is_empty());
+        assert!(diagnose(&mut db, true).is_healthy());
+        assert_eq!(db, before);
+    }
+
+    #[test]
+    fn diagnose_should_report_and_fix_a_dangling_reference() {
+        let mut db = board();
+        let before = db.clone();
+        db.epics.get_mut(&1).unwrap().stories.push(7);
+
+        let issue = Issue::DanglingStory {
+            epic_id: 1,
+            story_id: 7,
+        };
+        assert_eq!(check_references(&db), vec![issue]);
+        assert_eq!(
+            diagnose(&mut db, false),
+            Report {
+                issues: vec![Issue::DanglingStory {
+                    epic_id: 1,
+                    story_id: 7,
+                }],
+                fixed: vec![],
+            }
+        );
+
+        let report = diagnose(&mut db, true);
+        assert!(report.is_healthy());
+        assert_eq!(report.fixed.len(), 1);
+        assert_eq!(db, before);
+    }
+
+    #[test]
+    fn diagnose_should_keep_the_first_of_duplicated_stories() {
+        let mut db = board();
+        db.last_item_id = 3;
+        db.stories
+            .insert(3, Story::new("".to_owned(), "".to_owned()));
+        db.epics.get_mut(&1).unwrap().stories = vec![2, 3, 2, 2];
+
+        assert_eq!(
+            check_duplicates(&db),
+            vec![Issue::DuplicateStory {
+                epic_id: 1,
+                story_id: 2,
+            }]
+        );
+
+        let report = diagnose(&mut db, true);
+        assert!(report.is_healthy());
+        assert_eq!(report.fixed.len(), 1);
+        assert_eq!(db.epics[&1].stories, vec![2, 3]);
+    }
+
+    #[test]
+    fn checks_should_find_stale_ids_and_orphans() {
+        let mut db = board();
+        db.last_item_id = 1;
+        db.stories
+            .insert(3, Story::new("".to_owned(), "".to_owned()));
+
+        assert_eq!(
+            run_checks(&db),
+            vec![
+                Issue::StaleLastItemId {
+                    last_item_id: 1,
+                    max_id: 3,
+                },
+                Issue::OrphanedStory { story_id: 3 },
+            ]
+        );
+
+        let report = diagnose(&mut db, true);
+        assert_eq!(report.issues, vec![Issue::OrphanedStory { story_id: 3 }]);
+        assert_eq!(db.last_item_id, 3);
+    }
+}
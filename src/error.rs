@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum JiraError {
+    #[error("No epic found with id {0}")]
+    EpicNotFound(u32),
+    #[error("No story found with id {0}")]
+    StoryNotFound(u32),
+    #[error("Story with id {story_id} has no subtask {number}")]
+    SubtaskNotFound { story_id: u32, number: usize },
+    #[error("No item ids left: last item id is {0}")]
+    IdsExhausted(u32),
+    #[error("{0}")]
+    InvalidOperation(String),
+    #[error("line {line}: {reason}")]
+    MalformedCsv { line: usize, reason: String },
+    #[error("data directory is not writable: {0}")]
+    NotWritable(String),
+    #[error("the board is open read-only")]
+    ReadOnly,
+    #[error("input was closed")]
+    InputClosed,
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    SerdeError(#[from] serde_json::Error),
+}
+
+pub type Result<T, E = JiraError> = std::result::Result<T, E>;
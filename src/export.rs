@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+
+use crate::models::{DBState, ItemRef};
+
+pub trait Exporter {
+    fn export(&self, db_state: &DBState) -> Result<String>;
+}
+
+pub struct JsonExporter;
+
+impl Exporter for JsonExporter {
+    fn export(&self, db_state: &DBState) -> Result<String> {
+        Ok(serde_json::to_string_pretty(db_state)?)
+    }
+}
+
+pub struct CsvExporter;
+
+impl Exporter for CsvExporter {
+    fn export(&self, db_state: &DBState) -> Result<String> {
+        let mut lines = vec!["type,id,epic,name,status".to_owned()];
+
+        for (epic_id, epic) in db_state.epics.iter().sorted_by_key(|(id, _)| **id) {
+            lines.push(csv_row(&[
+                "epic",
+                &ItemRef::Epic(*epic_id).to_string(),
+                "",
+                &epic.name,
+                &epic.status.to_string(),
+            ]));
+
+            for story_id in &epic.stories {
+                if let Some(story) = db_state.stories.get(story_id) {
+                    lines.push(csv_row(&[
+                        "story",
+                        &ItemRef::Story(*story_id).to_string(),
+                        &ItemRef::Epic(*epic_id).to_string(),
+                        &story.name,
+                        &story.status.to_string(),
+                    ]));
+                }
+            }
+        }
+
+        Ok(lines.join("\n") + "\n")
+    }
+}
+
+fn csv_row(fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|field| match field.contains([',', '"', '\n']) {
+            true => format!("\"{}\"", field.replace('"', "\"\"")),
+            false => field.to_string(),
+        })
+        .join(",")
+}
+
+pub struct MarkdownExporter;
+
+impl Exporter for MarkdownExporter {
+    fn export(&self, db_state: &DBState) -> Result<String> {
+        let mut lines = vec![];
+        if let Some(label) = db_state.project_label() {
+            lines.push(format!("# {}", label));
+            lines.push(String::new());
+        }
+
+        for (epic_id, epic) in db_state.epics.iter().sorted_by_key(|(id, _)| **id) {
+            lines.push(format!(
+                "## {} {} ({})",
+                ItemRef::Epic(*epic_id),
+                epic.name,
+                epic.status
+            ));
+            lines.push(String::new());
+
+            for story_id in &epic.stories {
+                if let Some(story) = db_state.stories.get(story_id) {
+                    lines.push(format!(
+                        "- {} {} ({})",
+                        ItemRef::Story(*story_id),
+                        story.name,
+                        story.status
+                    ));
+                }
+            }
+            lines.push(String::new());
+        }
+
+        Ok(lines.join("\n"))
+    }
+}
+
+pub struct MermaidExporter;
+
+impl Exporter for MermaidExporter {
+    fn export(&self, db_state: &DBState) -> Result<String> {
+        Ok(mermaid_timeline(db_state))
+    }
+}
+
+pub fn mermaid_timeline(db_state: &DBState) -> String {
+    let (scheduled, unscheduled): (Vec<_>, Vec<_>) = db_state
+        .stories
+        .iter()
+        .sorted_by_key(|(id, story)| (story.due_date, **id))
+        .partition(|(_, story)| story.due_date.is_some());
+    let title = db_state
+        .project_label()
+        .unwrap_or_else(|| "Timeline".to_owned());
+
+    let mut lines = vec![
+        "gantt".to_owned(),
+        format!("    title {}", mermaid_text(&title)),
+        "    dateFormat YYYY-MM-DD".to_owned(),
+    ];
+    for (story_id, story) in scheduled {
+        let Some(due_date) = story.due_date else {
+            continue;
+        };
+        let days = story
+            .estimate_hours
+            .map_or(1, |hours| (hours / 8.0).ceil().max(1.0) as i64);
+        lines.push(format!(
+            "    {} :story{}, {}, {}",
+            mermaid_text(&story.name),
+            story_id,
+            due_date - chrono::Duration::days(days),
+            due_date
+        ));
+    }
+    if !unscheduled.is_empty() {
+        lines.push("%% unscheduled".to_owned());
+        for (story_id, story) in unscheduled {
+            lines.push(format!("%% {} {}", ItemRef::Story(*story_id), story.name));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn mermaid_text(text: &str) -> String {
+    let text = text.replace([':', ';', '#', '\n'], " ");
+    match text.trim() {
+        "" => "untitled".to_owned(),
+        text => text.to_owned(),
+    }
+}
+
+pub fn registry() -> BTreeMap<&'static str, Box<dyn Exporter>> {
+    let mut exporters: BTreeMap<&'static str, Box<dyn Exporter>> = BTreeMap::new();
+    exporters.insert("json", Box::new(JsonExporter));
+    exporters.insert("csv", Box::new(CsvExporter));
+    exporters.insert("markdown", Box::new(MarkdownExporter));
+    exporters.insert("mermaid", Box::new(MermaidExporter));
+    exporters
+}
+
+pub fn exporter_for(format: &str) -> Result<Box<dyn Exporter>> {
+    let mut exporters = registry();
+    let names = exporters.keys().join(", ");
+
+    exporters
+        .remove(format.to_ascii_lowercase().as_str())
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown export format: {} (expected one of {})",
+                format,
+                names
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::models::{Epic, Status, Story};
+
+    fn board() -> DBState {
+        let mut epic = Epic::new("Launch, v1".to_owned(), "".to_owned());
+        epic.stories = vec![2];
+        let mut story = Story::new("Write \"docs\"".to_owned(), "".to_owned());
+        story.status = Status::InProgress;
+
+        DBState {
+            last_item_id: 2,
+            epics: HashMap::from([(1, epic)]),
+            stories: HashMap::from([(2, story)]),
+            project_name: Some("Launch".to_owned()),
+            project_version: None,
+        }
+    }
+
+    #[test]
+    fn exporter_for_should_resolve_builtin_formats() {
+        for format in ["json", "csv", "markdown", "mermaid", "CSV"] {
+            assert!(exporter_for(format).is_ok(), "{}", format);
+        }
+        assert!(exporter_for("xml").is_err());
+    }
+
+    #[test]
+    fn json_exporter_should_round_trip() {
+        let db_state = board();
+        let output = JsonExporter.export(&db_state).unwrap();
+
+        assert_eq!(serde_json::from_str::<DBState>(&output).unwrap(), db_state);
+    }
+
+    #[test]
+    fn csv_exporter_should_escape_fields() {
+        let output = CsvExporter.export(&board()).unwrap();
+
+        assert_eq!(
+            output,
+            "type,id,epic,name,status\n\
+             epic,EPIC-1,,\"Launch, v1\",OPEN\n\
+             story,STORY-2,EPIC-1,\"Write \"\"docs\"\"\",IN PROGRESS\n"
+        );
+    }
+
+    #[test]
+    fn markdown_exporter_should_list_stories_under_epics() {
+        let output = MarkdownExporter.export(&board()).unwrap();
+
+        assert_eq!(
+            output,
+            "# Launch\n\n## EPIC-1 Launch, v1 (OPEN)\n\n- STORY-2 Write \"docs\" (IN PROGRESS)\n"
+        );
+    }
+}
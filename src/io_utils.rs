@@ -1,13 +1,250 @@
-use std::io;
+use std::{
+    cell::RefCell,
+    io::{self, BufRead, IsTerminal},
+    path::{Path, PathBuf},
+};
 
-pub fn get_user_input() -> String {
-    let mut user_input = String::new();
+use rustyline::{error::ReadlineError, history::MemHistory, Config, Editor};
 
-    io::stdin().read_line(&mut user_input).unwrap();
+thread_local! {
+    static INPUT_EDITOR: RefCell<InputEditor> = RefCell::new(InputEditor::new());
+}
+
+pub trait LineEditor {
+    fn readline(&mut self) -> rustyline::Result<String>;
+    fn add_history_entry(&mut self, line: &str);
+}
+
+impl LineEditor for Editor<(), MemHistory> {
+    fn readline(&mut self) -> rustyline::Result<String> {
+        Editor::readline(self, "")
+    }
 
-    user_input
+    fn add_history_entry(&mut self, line: &str) {
+        let _ = Editor::add_history_entry(self, line);
+    }
+}
+
+pub struct InputEditor {
+    editor: Option<Box<dyn LineEditor>>,
+    history: Vec<String>,
+}
+
+impl InputEditor {
+    pub fn new() -> Self {
+        let editor = match io::stdin().is_terminal() {
+            true => Editor::with_history(Config::default(), MemHistory::new())
+                .ok()
+                .map(|editor| Box::new(editor) as Box<dyn LineEditor>),
+            false => None,
+        };
+
+        Self {
+            editor,
+            history: vec![],
+        }
+    }
+
+    pub fn read_line(&mut self) -> Option<String> {
+        let line = match &mut self.editor {
+            Some(editor) => match editor.readline() {
+                Ok(line) => line + "\n",
+                Err(ReadlineError::Interrupted | ReadlineError::Eof) => return None,
+                Err(_) => "\n".to_owned(),
+            },
+            None => {
+                Some(read_line_lossy(&mut io::stdin().lock())).filter(|line| !line.is_empty())?
+            }
+        };
+
+        self.add_history(&line);
+        Some(line)
+    }
+
+    pub fn add_history(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() || self.history.last().is_some_and(|last| last == line) {
+            return;
+        }
+
+        if let Some(editor) = &mut self.editor {
+            editor.add_history_entry(line);
+        }
+        self.history.push(line.to_owned());
+    }
+
+    #[cfg(test)]
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+}
+
+pub fn get_user_input() -> Option<String> {
+    INPUT_EDITOR.with(|editor| editor.borrow_mut().read_line())
+}
+
+pub fn read_line_lossy(reader: &mut impl BufRead) -> String {
+    let mut bytes = vec![];
+    let _ = reader.read_until(b'\n', &mut bytes);
+
+    String::from_utf8_lossy(&bytes).into_owned()
 }
 
 pub fn wait_for_key_press() {
-    io::stdin().read_line(&mut String::new()).unwrap();
-}
\ No newline at end of file
+    read_line_lossy(&mut io::stdin().lock());
+}
+
+pub fn should_use_ansi(stdout_is_terminal: bool, term: Option<&str>) -> bool {
+    stdout_is_terminal && term != Some("dumb")
+}
+
+pub fn stdout_supports_ansi() -> bool {
+    should_use_ansi(
+        io::stdout().is_terminal(),
+        std::env::var("TERM").ok().as_deref(),
+    )
+}
+
+pub fn terminal_height() -> usize {
+    terminal_dimension("LINES", 24)
+}
+
+pub fn terminal_width() -> usize {
+    terminal_dimension("COLUMNS", 80)
+}
+
+fn terminal_dimension(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(default)
+}
+
+pub fn resolve_default_path(path: &str, exe_path: Option<&Path>) -> PathBuf {
+    match exe_path.and_then(Path::parent) {
+        Some(dir) => dir.join(path.trim_start_matches("./")),
+        None => PathBuf::from(path),
+    }
+}
+
+pub trait Notifier {
+    fn notify(&self, message: &str);
+}
+
+pub struct Bell;
+
+impl Notifier for Bell {
+    fn notify(&self, message: &str) {
+        eprintln!("\x07{}", message);
+    }
+}
+
+pub fn run_notified<T>(
+    notifier: Option<&dyn Notifier>,
+    message: &str,
+    operation: impl FnOnce() -> T,
+) -> T {
+    let result = operation();
+    if let Some(notifier) = notifier {
+        notifier.notify(message);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_use_ansi_should_require_a_capable_terminal() {
+        assert!(should_use_ansi(true, Some("xterm-256color")));
+        assert!(should_use_ansi(true, None));
+        assert!(!should_use_ansi(false, Some("xterm-256color")));
+        assert!(!should_use_ansi(true, Some("dumb")));
+    }
+
+    #[test]
+    fn resolve_default_path_should_follow_the_executable_when_given() {
+        assert_eq!(
+            resolve_default_path("./data/db.json", None),
+            PathBuf::from("./data/db.json")
+        );
+        assert_eq!(
+            resolve_default_path("./data/db.json", Some(Path::new("/opt/jirrah/jirrah"))),
+            PathBuf::from("/opt/jirrah/data/db.json")
+        );
+    }
+
+    #[test]
+    fn run_notified_should_notify_after_the_operation() {
+        struct Recorder(RefCell<Vec<String>>);
+
+        impl Notifier for Recorder {
+            fn notify(&self, message: &str) {
+                self.0.borrow_mut().push(message.to_owned());
+            }
+        }
+
+        let recorder = Recorder(RefCell::new(vec![]));
+        let result = run_notified(Some(&recorder), "import finished", || {
+            assert!(recorder.0.borrow().is_empty());
+            42
+        });
+
+        assert_eq!(result, 42);
+        assert_eq!(*recorder.0.borrow(), vec!["import finished"]);
+        assert_eq!(run_notified(None, "export finished", || 7), 7);
+    }
+
+    #[test]
+    fn input_editor_should_remember_previous_entries() {
+        let mut editor = InputEditor {
+            editor: None,
+            history: vec![],
+        };
+
+        editor.add_history("First story\n");
+        editor.add_history("");
+        editor.add_history("Second story");
+        editor.add_history("Second story");
+
+        assert_eq!(editor.history(), ["First story", "Second story"]);
+    }
+
+    #[test]
+    fn input_editor_should_stop_reading_on_eof_or_interrupt() {
+        struct Scripted(Vec<rustyline::Result<String>>);
+
+        impl LineEditor for Scripted {
+            fn readline(&mut self) -> rustyline::Result<String> {
+                self.0.remove(0)
+            }
+
+            fn add_history_entry(&mut self, _line: &str) {}
+        }
+
+        let mut editor = InputEditor {
+            editor: Some(Box::new(Scripted(vec![
+                Ok("First story".to_owned()),
+                Err(ReadlineError::Eof),
+                Err(ReadlineError::Interrupted),
+            ]))),
+            history: vec![],
+        };
+
+        assert_eq!(editor.read_line().as_deref(), Some("First story\n"));
+        assert_eq!(editor.read_line(), None);
+        assert_eq!(editor.read_line(), None);
+        assert_eq!(editor.history(), ["First story"]);
+    }
+
+    #[test]
+    fn read_line_lossy_should_replace_invalid_utf8() {
+        let mut input: &[u8] = b"story \xff\xfe name\nnext\n";
+
+        assert_eq!(read_line_lossy(&mut input), "story \u{fffd}\u{fffd} name\n");
+        assert_eq!(read_line_lossy(&mut input), "next\n");
+        assert_eq!(read_line_lossy(&mut input), "");
+    }
+}
@@ -1,28 +1,422 @@
 use std::rc::Rc;
 
-use crate::io_utils::wait_for_key_press;
+use crate::cli::{Args, Command};
+use crate::io_utils::{
+    resolve_default_path, run_notified, stdout_supports_ansi, wait_for_key_press, Bell, Notifier,
+};
 
 pub mod db;
 
+mod cli;
+mod config;
+mod diff;
+mod doctor;
+mod error;
+mod export;
 mod io_utils;
 mod models;
 mod navigator;
 mod ui;
+mod watch;
+
+const DEFAULT_DB_PATH: &str = "./data/db.json";
+const DEFAULT_STALE_DAYS: u32 = 14;
 
 fn main() {
-    let database = db::JiraDatabase::new("./data/db.json");
-    let mut navigator = navigator::Navigator::new(Rc::new(database));
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        }
+    };
+    let config = match is_first_run(&args) {
+        true => run_setup_wizard(),
+        false => config::Config::load(),
+    };
+    let config = match config {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("Error reading config: {}", error);
+            std::process::exit(2);
+        }
+    };
+    let args = config.apply(args);
+
+    match &args.command {
+        Command::Interactive => match args.watch_epic {
+            Some(epic_id) => run_watch(&args, &config, epic_id),
+            None => run_interactive(&args, &config),
+        },
+        Command::Schema => match db::db_schema() {
+            Ok(schema) => println!("{}", schema),
+            Err(error) => {
+                eprintln!("Error generating schema: {}", error);
+                std::process::exit(1);
+            }
+        },
+        Command::Diff { old, new } => run_diff(old, new),
+        Command::Export { format } => run_export(&args, format),
+        Command::Repair => run_repair(&args),
+        Command::Standup => run_standup(&args),
+        Command::Doctor { fix, json } => run_doctor(&args, *fix, *json),
+        Command::Reset { keep_settings } => run_reset(&args, *keep_settings),
+        Command::Project { name, version } => run_project(&args, name.clone(), version.clone()),
+        Command::Import { epic_id, path } => run_import(&args, *epic_id, path),
+    }
+}
+
+fn run_diff(old: &str, new: &str) {
+    let read = |path: &str| {
+        db::JiraDatabase::new(path)
+            .read_db()
+            .unwrap_or_else(|error| {
+                eprintln!("Error reading {}: {}", path, error);
+                std::process::exit(1);
+            })
+    };
+
+    print!("{}", diff::diff_states(&read(old), &read(new)));
+}
+
+fn db_path(args: &Args) -> String {
+    args.db_path.clone().unwrap_or_else(|| {
+        let exe_path = match args.exe_relative {
+            true => std::env::current_exe().ok(),
+            false => None,
+        };
+        resolve_default_path(DEFAULT_DB_PATH, exe_path.as_deref())
+            .display()
+            .to_string()
+    })
+}
+
+fn is_first_run(args: &Args) -> bool {
+    let db_path = db_path(args);
+
+    args.command == Command::Interactive
+        && !args.no_wizard
+        && !args.read_only
+        && !config::Config::exists()
+        && !std::path::Path::new(&db_path).exists()
+}
+
+fn run_setup_wizard() -> anyhow::Result<config::Config> {
+    println!("Welcome to jirrah! Let's set up your board (run with --no-wizard to skip).");
+    let config = config::Config::from_wizard(&mut std::io::stdin().lock());
+
+    let path = config::Config::user_path();
+    config.save(&path)?;
+    println!("Saved settings to {}", path.display());
+
+    Ok(config)
+}
+
+fn run_export(args: &Args, format: &str) {
+    let output = run_notified(notifier(args), "export finished", || {
+        export::exporter_for(format).and_then(|exporter| {
+            let db_state = open_existing_board(args)?.read_db()?;
+            exporter.export(&db_state)
+        })
+    });
+
+    match output {
+        Ok(output) => print!("{}", output),
+        Err(error) => {
+            eprintln!("Error exporting board: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_project(args: &Args, name: Option<String>, version: Option<String>) {
+    let updated = open_board(args).and_then(|database| database.set_project(name, version));
+
+    if let Err(error) = updated {
+        eprintln!("Error updating project: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run_reset(args: &Args, keep_settings: bool) {
+    let database = open_board(args).and_then(|database| {
+        let db_state = database.read_db()?;
+        Ok((database, db_state))
+    });
+    let (database, db_state) = match database {
+        Ok(opened) => opened,
+        Err(error) => {
+            eprintln!("Error opening board: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    println!(
+        "This deletes all {} epics and {} stories in {}. Type yes to continue:",
+        db_state.epics.len(),
+        db_state.stories.len(),
+        db_path(args)
+    );
+    let answer = io_utils::read_line_lossy(&mut std::io::stdin().lock());
+    if cli::parse_yes_no(&answer) != Some(true) {
+        println!("Reset cancelled");
+        return;
+    }
+
+    if let Err(error) = database.reset(keep_settings, args.id_start) {
+        eprintln!("Error resetting board: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run_repair(args: &Args) {
+    let repaired = open_board(args).and_then(|database| database.repair_item_id());
+
+    match repaired {
+        Ok(true) => println!("Raised last_item_id to the highest id on the board"),
+        Ok(false) => println!("last_item_id is consistent, nothing to repair"),
+        Err(error) => {
+            eprintln!("Error repairing board: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_standup(args: &Args) {
+    match open_board(args).and_then(|database| database.standup_report()) {
+        Ok(report) => print!("{}", report),
+        Err(error) => {
+            eprintln!("Error building standup report: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_doctor(args: &Args, fix: bool, json: bool) {
+    let report = open_board(args).and_then(|database| match fix {
+        true => database.transaction(|db_state| Ok(doctor::diagnose(db_state, true))),
+        false => Ok(doctor::diagnose(&mut database.read_db()?, false)),
+    });
+    let report = match report {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("Error checking board: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(error) => {
+                eprintln!("Error writing report: {}", error);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        for issue in &report.fixed {
+            println!("fixed: {}", issue);
+        }
+        for issue in &report.issues {
+            println!("issue: {}", issue);
+        }
+        if report.is_healthy() {
+            println!("No issues found");
+        }
+    }
+
+    if !report.is_healthy() {
+        std::process::exit(1);
+    }
+}
+
+fn run_import(args: &Args, epic_id: u32, csv_path: &str) {
+    let imported = run_notified(notifier(args), "import finished", || {
+        open_board(args).and_then(|database| database.import_stories_csv(epic_id, csv_path))
+    });
+
+    match imported {
+        Ok(import) => {
+            for (line, warning) in &import.warnings {
+                eprintln!("warning: line {}: {}", line, warning);
+            }
+            println!("Imported {} stories", import.story_ids.len());
+        }
+        Err(error) => {
+            eprintln!("Error importing {}: {}", csv_path, error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn notifier(args: &Args) -> Option<&'static dyn Notifier> {
+    args.bell.then_some(&Bell)
+}
+
+fn open_board(args: &Args) -> error::Result<db::JiraDatabase> {
+    let path = &db_path(args);
+    let created = !std::path::Path::new(path).exists();
+    let database = match (args.read_only, args.delta) {
+        (true, true) => db::JiraDatabase::open_delta_file(path, args.compact)?,
+        (true, false) => db::JiraDatabase::open_json_file(path, args.compact)?,
+        (false, true) => db::JiraDatabase::init_delta_file(path, args.compact)?,
+        (false, false) => db::JiraDatabase::init_json_file(path, args.compact)?,
+    };
+    if let Some(id_start) = args.id_start.filter(|_| created) {
+        database.seed_id_start(id_start)?;
+    }
+
+    Ok(with_board_options(args, database))
+}
+
+fn open_existing_board(args: &Args) -> error::Result<db::JiraDatabase> {
+    let path = &db_path(args);
+    let database = match args.delta {
+        true => db::JiraDatabase::open_delta_file(path, args.compact)?,
+        false => db::JiraDatabase::open_json_file(path, args.compact)?,
+    };
+
+    Ok(with_board_options(args, database))
+}
+
+fn with_board_options(args: &Args, database: db::JiraDatabase) -> db::JiraDatabase {
+    let database = match &args.log_path {
+        Some(log_path) => database.logged(log_path),
+        None => database,
+    };
+    match args.read_only {
+        true => database.read_only(),
+        false => database,
+    }
+}
+
+fn display_options(args: &Args, config: &config::Config, ansi: bool) -> ui::DisplayOptions {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_default();
+
+    ui::DisplayOptions {
+        ansi,
+        emoji: args.emoji && ui::supports_utf8(&locale),
+        page_size: config.page_size,
+        show_history: args.history,
+        show_snoozed: args.show_snoozed,
+        stale_days: config.stale_days.unwrap_or(DEFAULT_STALE_DAYS),
+        progress_width: config.progress_width,
+        read_only: args.read_only,
+        keys: config.keys.clone().unwrap_or_default(),
+    }
+}
+
+fn run_watch(args: &Args, config: &config::Config, epic_id: u32) {
+    let ansi = stdout_supports_ansi();
+    let options = Rc::new(display_options(args, config, ansi));
+    let epic = models::ItemRef::Epic(epic_id);
+    let mut previous: Option<models::DBState> = None;
 
     loop {
-        clearscreen::clear().unwrap();
+        let opened = open_existing_board(args).and_then(|database| {
+            let db_state = database.read_db()?;
+            Ok((Rc::new(database), db_state))
+        });
+        let (database, db_state) = match opened {
+            Ok(opened) => opened,
+            Err(error) => {
+                eprintln!("Error reading board: {}", error);
+                std::process::exit(1);
+            }
+        };
+
+        let event = match &previous {
+            Some(previous) => watch::watch_event(previous, &db_state, epic_id),
+            None if db_state.epics.contains_key(&epic_id) => watch::WatchEvent::Changed,
+            None => watch::WatchEvent::Deleted,
+        };
+        if event == watch::WatchEvent::Deleted {
+            eprintln!("{} no longer exists, stopped watching", epic);
+            std::process::exit(1);
+        }
+        if event != watch::WatchEvent::Unchanged {
+            if ansi {
+                clearscreen::clear().unwrap();
+            }
+            let page = ui::EpicDetail {
+                epic_id,
+                db: database,
+                options: Rc::clone(&options),
+                sort: models::SortOrder::default(),
+            };
+            if let Err(error) = ui::Page::draw_page(&page, &mut std::io::stdout()) {
+                eprintln!("Error rendering page: {}", error);
+            }
+        }
+        if event == watch::WatchEvent::Closed
+            || previous.is_none() && db_state.epics[&epic_id].status == models::Status::Closed
+        {
+            let closed = format!("{} is closed", epic);
+            match ansi {
+                true => Bell.notify(&closed),
+                false => println!("{}", closed),
+            }
+            return;
+        }
+
+        previous = Some(db_state);
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+}
+
+fn run_interactive(args: &Args, config: &config::Config) {
+    let mut database = match open_board(args) {
+        Ok(database) => database,
+        Err(error) => {
+            eprintln!("Error opening board: {}", error);
+            std::process::exit(1);
+        }
+    };
+    if args.batch && !args.read_only {
+        database = database.batched();
+    }
+    database = database
+        .with_strict_delete(args.strict_delete)
+        .with_resolve_on_complete(args.resolve_on_complete)
+        .with_strict_dod(args.strict_dod);
+    if let Some(done_statuses) = &args.done_statuses {
+        database = database.with_done_statuses(done_statuses.clone());
+    }
+    let database = Rc::new(database);
+    let ansi = stdout_supports_ansi();
+    let options = display_options(args, config, ansi);
+    let mut navigator = navigator::Navigator::with_options(Rc::clone(&database), options);
+
+    let confirm_default = args.confirm_default.or_else(|| {
+        std::env::var("JIRRAH_CONFIRM_DEFAULT")
+            .ok()
+            .and_then(|value| cli::parse_yes_no(&value))
+    });
+    navigator.set_prompts(ui::Prompts::with_confirm_default(
+        confirm_default.unwrap_or(false),
+    ));
+
+    loop {
+        if ansi {
+            clearscreen::clear().unwrap();
+        }
 
         let page = navigator.get_current_page();
         if page.is_none() {
+            if let Err(error) = database.flush() {
+                println!("Error saving changes: {}", error);
+            }
+            if !args.quiet {
+                print_farewell(&database);
+            }
             break;
         }
 
         if let Some(page) = page {
-            if let Err(error) = page.draw_page() {
+            if let Err(error) = page.draw_page(&mut std::io::stdout()) {
                 println!(
                     "Error rendering page: {}\nPress any key to continue...",
                     error
@@ -30,7 +424,15 @@ fn main() {
                 wait_for_key_press();
             }
 
-            let input = io_utils::get_user_input();
+            let input = match io_utils::get_user_input() {
+                Some(input) => input,
+                None => {
+                    if let Err(error) = navigator.handle_action(models::Action::Exit) {
+                        println!("Error saving changes: {}", error);
+                    }
+                    continue;
+                }
+            };
             match page.handle_input(input.trim()) {
                 Err(error) => {
                     println!(
@@ -54,3 +456,12 @@ fn main() {
         }
     }
 }
+
+fn print_farewell(database: &db::JiraDatabase) {
+    if let Ok(stats) = database.stats() {
+        println!(
+            "You have {} open stories across {} epics. Goodbye.",
+            stats.open_story_count, stats.epic_count
+        );
+    }
+}
@@ -1,22 +1,388 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{cmp::Ordering, collections::HashMap, fmt::Display, str::FromStr};
 
-use serde::{Deserialize, Serialize};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use itertools::Itertools;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Action {
-    NavigateToEpicDetail { epic_id: u32 },
-    NavigateToStoryDetail { epic_id: u32, story_id: u32 },
+    NavigateToEpicDetail {
+        epic_id: u32,
+    },
+    NavigateToStoryDetail {
+        epic_id: u32,
+        story_id: u32,
+    },
+    PageStoryDescription {
+        epic_id: u32,
+        story_id: u32,
+        page: usize,
+    },
+    NavigateToStoryFocus {
+        story_id: u32,
+    },
+    SnoozeStory {
+        story_id: u32,
+    },
+    SetStoryDueDate {
+        story_id: u32,
+    },
     NavigateToPreviousPage,
+    SortHomePage {
+        sort: SortOrder,
+    },
+    SortEpicDetail {
+        epic_id: u32,
+        sort: SortOrder,
+    },
+    GroupHomePage {
+        grouped: bool,
+    },
+    NavigateToStoriesByStatus,
+    NavigateToStaleStories,
+    NavigateToReadyQueue,
     CreateEpic,
-    UpdateEpicStatus { epic_id: u32 },
-    DeleteEpic { epic_id: u32 },
-    CreateStory { epic_id: u32 },
-    UpdateStoryStatus { story_id: u32 },
-    DeleteStory { epic_id: u32, story_id: u32 },
+    UpdateEpicStatus {
+        epic_id: u32,
+    },
+    DeleteEpic {
+        epic_id: u32,
+    },
+    DeleteEpicKeepStories {
+        epic_id: u32,
+    },
+    MergeEpic {
+        epic_id: u32,
+    },
+    AddDodItem {
+        epic_id: u32,
+    },
+    ToggleDodItem {
+        epic_id: u32,
+    },
+    CreateStory {
+        epic_id: u32,
+    },
+    CreateStoryInPickedEpic,
+    QuickAddStory,
+    ReplaceText,
+    UpdateStoryStatus {
+        story_id: u32,
+    },
+    ReopenStory {
+        story_id: u32,
+    },
+    CreateSubtask {
+        story_id: u32,
+    },
+    ExtractSubtasks {
+        story_id: u32,
+    },
+    ToggleSubtask {
+        story_id: u32,
+    },
+    ToggleEpicStar {
+        epic_id: u32,
+    },
+    ToggleStoryPin {
+        story_id: u32,
+    },
+    TrackStoryTime {
+        story_id: u32,
+    },
+    AssignStory {
+        story_id: u32,
+    },
+    SetStoryPercent {
+        story_id: u32,
+    },
+    EditEpic {
+        epic_id: u32,
+    },
+    EditStory {
+        story_id: u32,
+    },
+    DeleteStory {
+        epic_id: u32,
+        story_id: u32,
+    },
+    GoTo,
     Exit,
 }
 
-#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, Clone)]
+impl Action {
+    pub fn mutates(&self) -> bool {
+        !matches!(
+            self,
+            Action::NavigateToEpicDetail { .. }
+                | Action::NavigateToStoryDetail { .. }
+                | Action::PageStoryDescription { .. }
+                | Action::NavigateToStoryFocus { .. }
+                | Action::NavigateToPreviousPage
+                | Action::SortHomePage { .. }
+                | Action::SortEpicDetail { .. }
+                | Action::GroupHomePage { .. }
+                | Action::NavigateToStoriesByStatus
+                | Action::NavigateToStaleStories
+                | Action::NavigateToReadyQueue
+                | Action::GoTo
+                | Action::Exit
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ItemRef {
+    Epic(u32),
+    Story(u32),
+    Any(u32),
+}
+
+impl Display for ItemRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemRef::Epic(id) => write!(f, "EPIC-{}", id),
+            ItemRef::Story(id) => write!(f, "STORY-{}", id),
+            ItemRef::Any(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+impl ItemRef {
+    pub fn id(&self) -> u32 {
+        match self {
+            ItemRef::Epic(id) | ItemRef::Story(id) | ItemRef::Any(id) => *id,
+        }
+    }
+}
+
+pub fn parse_item_ref(input: &str) -> Option<ItemRef> {
+    let input = input.trim();
+    if let Ok(id) = input.parse::<u32>() {
+        return Some(ItemRef::Any(id));
+    }
+
+    let (prefix, id) = input.split_once('-')?;
+    let id = id.parse::<u32>().ok()?;
+    match prefix.to_ascii_uppercase().as_str() {
+        "EPIC" => Some(ItemRef::Epic(id)),
+        "STORY" => Some(ItemRef::Story(id)),
+        _ => None,
+    }
+}
+
+pub fn parse_date_input(input: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let input = input.trim().to_ascii_lowercase();
+    let invalid = || {
+        anyhow!(
+            "invalid date \"{}\": expected YYYY-MM-DD, today, tomorrow, +Nd, +Nw or next <weekday>",
+            input
+        )
+    };
+
+    match input.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + Duration::days(1)),
+        _ => {}
+    }
+    if let Some(offset) = input.strip_prefix('+') {
+        let (count, unit) = offset.split_at(offset.len().saturating_sub(1));
+        let count = count.parse::<i64>().map_err(|_| invalid())?;
+        let days = match unit {
+            "d" => count,
+            "w" => count * 7,
+            _ => return Err(invalid()),
+        };
+        return today
+            .checked_add_signed(Duration::days(days))
+            .ok_or_else(invalid);
+    }
+    if let Some(weekday) = input.strip_prefix("next ") {
+        let weekday = weekday.trim().parse::<Weekday>().map_err(|_| invalid())?;
+        let days_ahead =
+            (weekday.num_days_from_monday() + 6 - today.weekday().num_days_from_monday()) % 7 + 1;
+        return Ok(today + Duration::days(days_ahead.into()));
+    }
+
+    NaiveDate::parse_from_str(&input, "%Y-%m-%d").map_err(|_| invalid())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct QuickAdd {
+    pub name: String,
+    pub epic_id: Option<u32>,
+    pub assignees: Vec<String>,
+}
+
+pub fn parse_quick_add(input: &str) -> Result<QuickAdd> {
+    let mut tokens = vec![];
+    let mut chars = input.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '"' => {
+                let mut quoted = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        closed = true;
+                        break;
+                    }
+                    quoted.push(c);
+                }
+                if !closed {
+                    return Err(anyhow!("unterminated quote in \"{}\"", input.trim()));
+                }
+                tokens.push((true, quoted));
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    word.push(c);
+                }
+                tokens.push((false, word));
+            }
+        }
+    }
+
+    let mut tokens = tokens.into_iter().peekable();
+    tokens.next_if(|(quoted, word)| !quoted && word.eq_ignore_ascii_case("new"));
+
+    let mut name = vec![];
+    let mut epic_id = None;
+    let mut assignees = vec![];
+    while let Some((quoted, token)) = tokens.next() {
+        if quoted {
+            name.push(token);
+            continue;
+        }
+
+        match token.chars().next() {
+            Some('@') if token.len() > 1 => assignees.push(token[1..].to_owned()),
+            Some('@') => return Err(anyhow!("@ expects an assignee name")),
+            Some('!') => return Err(anyhow!("stories have no priority to set with {}", token)),
+            Some('#') => return Err(anyhow!("stories have no labels to set with {}", token)),
+            _ if token.eq_ignore_ascii_case("under") => {
+                let target = tokens.next().map(|(_, target)| target).unwrap_or_default();
+                let id = match parse_item_ref(&target) {
+                    Some(ItemRef::Epic(id)) | Some(ItemRef::Any(id)) => id,
+                    _ => return Err(anyhow!("under expects an epic id, got \"{}\"", target)),
+                };
+                if epic_id.replace(id).is_some() {
+                    return Err(anyhow!("under can only be given once"));
+                }
+            }
+            _ => name.push(token),
+        }
+    }
+
+    let name = name.join(" ").trim().to_owned();
+    if name.is_empty() {
+        return Err(anyhow!("quick add needs a story name"));
+    }
+
+    Ok(QuickAdd {
+        name,
+        epic_id,
+        assignees,
+    })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ResolveResult {
+    Unique(ItemRef),
+    Ambiguous(Vec<ItemRef>),
+    None,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SortKey {
+    #[default]
+    Id,
+    Name,
+    Status,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct SortOrder {
+    pub key: SortKey,
+    pub direction: SortDirection,
+}
+
+pub trait Sortable {
+    fn sort_name(&self) -> &str;
+    fn sort_status(&self) -> &Status;
+}
+
+impl SortOrder {
+    pub fn next(self) -> Self {
+        match self.direction {
+            SortDirection::Ascending => Self {
+                key: self.key,
+                direction: SortDirection::Descending,
+            },
+            SortDirection::Descending => Self {
+                key: match self.key {
+                    SortKey::Id => SortKey::Name,
+                    SortKey::Name => SortKey::Status,
+                    SortKey::Status => SortKey::Id,
+                },
+                direction: SortDirection::Ascending,
+            },
+        }
+    }
+
+    pub fn compare<T: Sortable>(&self, a: (&u32, &T), b: (&u32, &T)) -> Ordering {
+        let ordering = match self.key {
+            SortKey::Id => a.0.cmp(b.0),
+            SortKey::Name => {
+                a.1.sort_name()
+                    .to_lowercase()
+                    .cmp(&b.1.sort_name().to_lowercase())
+                    .then(a.0.cmp(b.0))
+            }
+            SortKey::Status => {
+                a.1.sort_status()
+                    .rank()
+                    .cmp(&b.1.sort_status().rank())
+                    .then(a.0.cmp(b.0))
+            }
+        };
+
+        match self.direction {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    }
+}
+
+impl Display for SortOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key = match self.key {
+            SortKey::Id => "id",
+            SortKey::Name => "name",
+            SortKey::Status => "status",
+        };
+        let direction = match self.direction {
+            SortDirection::Ascending => "ascending",
+            SortDirection::Descending => "descending",
+        };
+
+        write!(f, "{} ({})", key, direction)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub enum Status {
     InProgress,
     Closed,
@@ -24,47 +390,380 @@ pub enum Status {
     Resolved,
 }
 
-#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, Clone)]
+#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct Epic {
     pub name: String,
     pub description: String,
     pub status: Status,
     pub stories: Vec<u32>,
+    #[serde(default)]
+    pub starred: bool,
+    #[serde(default)]
+    pub slug: String,
+    #[serde(default)]
+    pub dod: Vec<(String, bool)>,
 }
 
 impl Epic {
     pub fn new(name: String, description: String) -> Self {
         Self {
+            slug: slugify(&name),
             name,
             description,
             status: Status::Open,
             stories: Vec::new(),
+            starred: false,
+            dod: Vec::new(),
         }
     }
+
+    pub fn toggle_dod_item(&mut self, index: usize) -> Option<bool> {
+        let (_, done) = self.dod.get_mut(index)?;
+        *done = !*done;
+        Some(*done)
+    }
+
+    pub fn unchecked_dod(&self) -> Vec<&str> {
+        self.dod
+            .iter()
+            .filter(|(_, done)| !done)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
 }
 
-#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone)]
+#[derive(PartialEq, Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct Story {
     pub name: String,
     pub description: String,
     pub status: Status,
+    #[serde(default)]
+    pub subtasks: Vec<Subtask>,
+    #[serde(default)]
+    pub status_history: Vec<(Status, DateTime<Utc>)>,
+    #[serde(default)]
+    pub reopen_reasons: Vec<(DateTime<Utc>, String)>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub snoozed_until: Option<NaiveDate>,
+    #[serde(default)]
+    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub slug: String,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub estimate_hours: Option<f32>,
+    #[serde(default)]
+    pub actual_hours: Option<f32>,
+    #[serde(default)]
+    pub percent_complete: u8,
+    #[serde(default, alias = "assignee", deserialize_with = "one_or_many")]
+    #[schemars(with = "Vec<String>")]
+    pub assignees: Vec<String>,
+}
+
+fn sorted_by_id<S: Serializer, T: Serialize>(
+    items: &HashMap<u32, T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(items.iter().sorted_by_key(|(id, _)| **id))
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        Some(OneOrMany::One(name)) => vec![name],
+        Some(OneOrMany::Many(names)) => names,
+        None => vec![],
+    })
 }
 
 impl Story {
     pub fn new(name: String, description: String) -> Self {
         Self {
+            slug: slugify(&name),
             name,
             description,
             status: Status::Open,
+            subtasks: Vec::new(),
+            status_history: vec![(Status::Open, Utc::now())],
+            reopen_reasons: Vec::new(),
+            updated_at: Some(Utc::now()),
+            snoozed_until: None,
+            due_date: None,
+            pinned: false,
+            estimate_hours: None,
+            actual_hours: None,
+            percent_complete: 0,
+            assignees: Vec::new(),
+        }
+    }
+
+    pub fn last_updated(&self) -> Option<DateTime<Utc>> {
+        self.updated_at.or_else(|| {
+            self.status_history
+                .last()
+                .map(|(_, changed_at)| *changed_at)
+        })
+    }
+
+    pub fn is_snoozed(&self, today: NaiveDate) -> bool {
+        self.snoozed_until.is_some_and(|until| until > today)
+    }
+
+    pub fn set_status(&mut self, status: Status, at: DateTime<Utc>) {
+        if self.status == status {
+            return;
+        }
+
+        self.status_history.push((status.clone(), at));
+        self.status = status;
+    }
+
+    pub fn add_subtask(&mut self, name: String) {
+        self.subtasks.push(Subtask { name, done: false });
+    }
+
+    pub fn extract_subtasks(&mut self) -> usize {
+        let mut remaining = vec![];
+        let mut extracted = 0;
+
+        for line in self.description.lines() {
+            let Some(item) = line.trim_start().strip_prefix("- ") else {
+                remaining.push(line);
+                continue;
+            };
+            let (done, name) = match item.strip_prefix("[x] ").or(item.strip_prefix("[X] ")) {
+                Some(name) => (true, name),
+                None => (false, item.strip_prefix("[ ] ").unwrap_or(item)),
+            };
+            if name.trim().is_empty() {
+                remaining.push(line);
+                continue;
+            }
+
+            self.subtasks.push(Subtask {
+                name: name.trim().to_owned(),
+                done,
+            });
+            extracted += 1;
+        }
+
+        self.description = remaining.join("\n").trim_end().to_owned();
+        extracted
+    }
+
+    pub fn toggle_subtask(&mut self, index: usize) -> Option<bool> {
+        let subtask = self.subtasks.get_mut(index)?;
+        subtask.done = !subtask.done;
+        Some(subtask.done)
+    }
+
+    pub fn completed_subtasks(&self) -> usize {
+        self.subtasks.iter().filter(|subtask| subtask.done).count()
+    }
+
+    pub fn add_assignee(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.is_assigned_to(name) {
+            return false;
+        }
+
+        self.assignees.push(name.to_owned());
+        true
+    }
+
+    pub fn remove_assignee(&mut self, name: &str) -> bool {
+        let count = self.assignees.len();
+        self.assignees
+            .retain(|assignee| assignee.to_lowercase() != name.trim().to_lowercase());
+        self.assignees.len() != count
+    }
+
+    pub fn is_assigned_to(&self, name: &str) -> bool {
+        self.assignees
+            .iter()
+            .any(|assignee| assignee.to_lowercase() == name.trim().to_lowercase())
+    }
+}
+
+pub fn slugify(name: &str) -> String {
+    let mut folded = String::new();
+    for c in name.to_lowercase().chars() {
+        match fold_to_ascii(c) {
+            Some(ascii) => folded.push_str(ascii),
+            None => folded.push(c),
         }
     }
+
+    let slug = folded
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .join("-");
+
+    match slug.is_empty() {
+        true => "item".to_owned(),
+        false => slug,
+    }
+}
+
+fn fold_to_ascii(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => "i",
+        'ł' | 'ľ' | 'ĺ' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' | 'ŕ' => "r",
+        'ś' | 'š' | 'ş' => "s",
+        'ß' => "ss",
+        'ť' | 'ţ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+
+    Some(ascii)
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct Subtask {
+    pub name: String,
+    pub done: bool,
 }
 
-#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, Clone)]
+#[derive(PartialEq, Debug, Default, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct DBState {
     pub last_item_id: u32,
+    #[serde(serialize_with = "sorted_by_id")]
     pub epics: HashMap<u32, Epic>,
+    #[serde(serialize_with = "sorted_by_id")]
     pub stories: HashMap<u32, Story>,
+    #[serde(default)]
+    pub project_name: Option<String>,
+    #[serde(default)]
+    pub project_version: Option<String>,
+}
+
+impl DBState {
+    pub fn project_label(&self) -> Option<String> {
+        match (&self.project_name, &self.project_version) {
+            (Some(name), Some(version)) => Some(format!("{} {}", name, version)),
+            (Some(label), None) | (None, Some(label)) => Some(label.clone()),
+            (None, None) => None,
+        }
+    }
+
+    pub fn epic_of(&self, story_id: u32) -> Option<u32> {
+        self.epics
+            .iter()
+            .filter(|(_, epic)| epic.stories.contains(&story_id))
+            .map(|(epic_id, _)| *epic_id)
+            .min()
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct CsvImport {
+    pub story_ids: Vec<u32>,
+    pub warnings: Vec<(usize, String)>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct TextFields {
+    pub names: bool,
+    pub descriptions: bool,
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct ItemEdit {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<Status>,
+}
+
+impl ItemEdit {
+    pub fn apply(self, name: &mut String, description: &mut String, status: &mut Status) {
+        if let Some(new_name) = self.name {
+            *name = new_name;
+        }
+        if let Some(new_description) = self.description {
+            *description = new_description;
+        }
+        if let Some(new_status) = self.status {
+            *status = new_status;
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct BoardStats {
+    pub epic_count: usize,
+    pub story_count: usize,
+    pub open_story_count: usize,
+}
+
+impl Status {
+    fn rank(&self) -> u8 {
+        match self {
+            Status::Open => 0,
+            Status::InProgress => 1,
+            Status::Resolved => 2,
+            Status::Closed => 3,
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Status::Open => "🟢",
+            Status::InProgress => "🟡",
+            Status::Resolved => "🔵",
+            Status::Closed => "⚫",
+        }
+    }
+
+    pub fn label(&self, emoji: bool) -> String {
+        match emoji {
+            true => format!("{} {}", self.icon(), self),
+            false => self.to_string(),
+        }
+    }
+}
+
+impl Sortable for Epic {
+    fn sort_name(&self) -> &str {
+        &self.name
+    }
+
+    fn sort_status(&self) -> &Status {
+        &self.status
+    }
+}
+
+impl Sortable for Story {
+    fn sort_name(&self) -> &str {
+        &self.name
+    }
+
+    fn sort_status(&self) -> &Status {
+        &self.status
+    }
 }
 
 impl Display for Status {
@@ -77,3 +776,371 @@ impl Display for Status {
         }
     }
 }
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s
+            .trim()
+            .to_ascii_lowercase()
+            .replace(['_', ' '], "-")
+            .as_str()
+        {
+            "open" => Ok(Status::Open),
+            "in-progress" | "inprogress" => Ok(Status::InProgress),
+            "resolved" => Ok(Status::Resolved),
+            "closed" => Ok(Status::Closed),
+            other => Err(format!("Unknown status: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn sort_order_next_should_flip_direction_before_key() {
+        let order = SortOrder::default();
+        assert_eq!(order.key, SortKey::Id);
+        assert_eq!(order.direction, SortDirection::Ascending);
+
+        let order = order.next();
+        assert_eq!(order.key, SortKey::Id);
+        assert_eq!(order.direction, SortDirection::Descending);
+
+        let order = order.next();
+        assert_eq!(order.key, SortKey::Name);
+        assert_eq!(order.direction, SortDirection::Ascending);
+
+        let order = order.next().next().next().next();
+        assert_eq!(order, SortOrder::default());
+    }
+
+    #[test]
+    fn sort_order_should_sort_by_id_in_both_directions() {
+        let mut epics = HashMap::new();
+        for id in [3, 1, 2] {
+            epics.insert(id, Epic::new(format!("epic {}", id), "".to_owned()));
+        }
+
+        let ascending = SortOrder::default();
+        let ids: Vec<u32> = epics
+            .iter()
+            .sorted_by(|a, b| ascending.compare(*a, *b))
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        let descending = SortOrder {
+            key: SortKey::Id,
+            direction: SortDirection::Descending,
+        };
+        let ids: Vec<u32> = epics
+            .iter()
+            .sorted_by(|a, b| descending.compare(*a, *b))
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(ids, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn sort_order_should_sort_by_status() {
+        let mut stories = HashMap::new();
+        for (id, status) in [
+            (1, Status::Closed),
+            (2, Status::Open),
+            (3, Status::InProgress),
+        ] {
+            let mut story = Story::new("".to_owned(), "".to_owned());
+            story.status = status;
+            stories.insert(id, story);
+        }
+
+        let order = SortOrder {
+            key: SortKey::Status,
+            direction: SortDirection::Ascending,
+        };
+        let ids: Vec<u32> = stories
+            .iter()
+            .sorted_by(|a, b| order.compare(*a, *b))
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn parse_item_ref_should_accept_prefixed_and_bare_ids() {
+        assert_eq!(parse_item_ref("EPIC-1"), Some(ItemRef::Epic(1)));
+        assert_eq!(parse_item_ref("story-3"), Some(ItemRef::Story(3)));
+        assert_eq!(parse_item_ref("7"), Some(ItemRef::Any(7)));
+        assert_eq!(parse_item_ref(" Epic-12 "), Some(ItemRef::Epic(12)));
+    }
+
+    #[test]
+    fn parse_item_ref_should_reject_junk() {
+        assert_eq!(parse_item_ref(""), None);
+        assert_eq!(parse_item_ref("EPIC-"), None);
+        assert_eq!(parse_item_ref("EPIC-x"), None);
+        assert_eq!(parse_item_ref("TASK-1"), None);
+        assert_eq!(parse_item_ref("-1"), None);
+    }
+
+    #[test]
+    fn item_ref_should_display_with_prefix() {
+        assert_eq!(ItemRef::Epic(1).to_string(), "EPIC-1");
+        assert_eq!(ItemRef::Story(3).to_string(), "STORY-3");
+        assert_eq!(ItemRef::Any(7).to_string(), "7");
+    }
+
+    #[test]
+    fn status_should_parse_from_str() {
+        assert_eq!("open".parse::<Status>(), Ok(Status::Open));
+        assert_eq!("In-Progress".parse::<Status>(), Ok(Status::InProgress));
+        assert_eq!("in_progress".parse::<Status>(), Ok(Status::InProgress));
+        assert_eq!("RESOLVED".parse::<Status>(), Ok(Status::Resolved));
+        assert_eq!("closed".parse::<Status>(), Ok(Status::Closed));
+        assert!("done".parse::<Status>().is_err());
+    }
+
+    #[test]
+    fn add_subtask_should_append_an_unfinished_subtask() {
+        let mut story = Story::new("".to_owned(), "".to_owned());
+
+        story.add_subtask("first".to_owned());
+        story.add_subtask("second".to_owned());
+
+        assert_eq!(
+            story.subtasks,
+            vec![
+                Subtask {
+                    name: "first".to_owned(),
+                    done: false
+                },
+                Subtask {
+                    name: "second".to_owned(),
+                    done: false
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn toggle_subtask_should_flip_done_flag() {
+        let mut story = Story::new("".to_owned(), "".to_owned());
+        story.add_subtask("first".to_owned());
+
+        assert_eq!(story.toggle_subtask(0), Some(true));
+        assert!(story.subtasks[0].done);
+        assert_eq!(story.toggle_subtask(0), Some(false));
+        assert!(!story.subtasks[0].done);
+        assert_eq!(story.toggle_subtask(1), None);
+    }
+
+    #[test]
+    fn assignees_should_be_deduplicated_ignoring_case() {
+        let mut story = Story::new("".to_owned(), "".to_owned());
+
+        assert!(story.add_assignee("Alice"));
+        assert!(!story.add_assignee(" alice "));
+        assert!(story.add_assignee("Bob"));
+        assert!(!story.add_assignee(""));
+        assert_eq!(story.assignees, vec!["Alice", "Bob"]);
+        assert!(story.is_assigned_to("ALICE"));
+
+        assert!(story.remove_assignee("BOB"));
+        assert!(!story.remove_assignee("bob"));
+        assert_eq!(story.assignees, vec!["Alice"]);
+    }
+
+    #[test]
+    fn story_should_migrate_a_single_assignee() {
+        let story: Story = serde_json::from_str(
+            r#"{ "name": "", "description": "", "status": "Open", "assignee": "alice" }"#,
+        )
+        .unwrap();
+        assert_eq!(story.assignees, vec!["alice"]);
+
+        let story: Story = serde_json::from_str(
+            r#"{ "name": "", "description": "", "status": "Open", "assignee": null }"#,
+        )
+        .unwrap();
+        assert!(story.assignees.is_empty());
+
+        let story: Story = serde_json::from_str(
+            r#"{ "name": "", "description": "", "status": "Open", "assignees": ["a", "b"] }"#,
+        )
+        .unwrap();
+        assert_eq!(story.assignees, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn completed_subtasks_should_count_done_subtasks() {
+        let mut story = Story::new("".to_owned(), "".to_owned());
+        assert_eq!(story.completed_subtasks(), 0);
+
+        story.add_subtask("first".to_owned());
+        story.add_subtask("second".to_owned());
+        story.add_subtask("third".to_owned());
+        story.toggle_subtask(0);
+        story.toggle_subtask(2);
+
+        assert_eq!(story.completed_subtasks(), 2);
+    }
+
+    #[test]
+    fn epic_without_starred_should_deserialize() {
+        let epic: Epic = serde_json::from_str(
+            r#"{ "name": "", "description": "", "status": "Open", "stories": [] }"#,
+        )
+        .unwrap();
+
+        assert!(!epic.starred);
+    }
+
+    #[test]
+    fn set_status_should_record_changes_only() {
+        let mut story = Story::new("".to_owned(), "".to_owned());
+        let created_at = story.status_history[0].1;
+        let at = Utc::now();
+
+        story.set_status(Status::Open, at);
+        story.set_status(Status::InProgress, at);
+
+        assert_eq!(story.status, Status::InProgress);
+        assert_eq!(
+            story.status_history,
+            vec![(Status::Open, created_at), (Status::InProgress, at)]
+        );
+    }
+
+    #[test]
+    fn story_without_subtasks_should_deserialize() {
+        let story: Story =
+            serde_json::from_str(r#"{ "name": "", "description": "", "status": "Open" }"#).unwrap();
+
+        assert!(story.subtasks.is_empty());
+        assert!(story.status_history.is_empty());
+    }
+
+    #[test]
+    fn epic_of_should_pick_the_lowest_epic_listing_the_story() {
+        let mut db = DBState::default();
+        for epic_id in [7, 3, 5] {
+            let mut epic = Epic::new("".to_owned(), "".to_owned());
+            epic.stories = match epic_id {
+                5 => vec![],
+                _ => vec![9],
+            };
+            db.epics.insert(epic_id, epic);
+        }
+
+        assert_eq!(db.epic_of(9), Some(3));
+        assert_eq!(db.epic_of(4), None);
+    }
+
+    #[test]
+    fn slugify_should_keep_words_and_drop_punctuation() {
+        assert_eq!(slugify("Login Flow"), "login-flow");
+        assert_eq!(slugify("  Fix: crash (on save)!! "), "fix-crash-on-save");
+        assert_eq!(slugify("Café déjà vu 🚀 v2"), "cafe-deja-vu-v2");
+        assert_eq!(slugify("Über Straße"), "uber-strasse");
+        assert_eq!(slugify("Łódź Привет"), "lodz");
+        assert_eq!(slugify("🚀 ✨"), "item");
+    }
+
+    #[test]
+    fn parse_date_input_should_resolve_relative_dates() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let date = |month, day| NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+
+        assert_eq!(parse_date_input("tomorrow", today).unwrap(), date(10, 15));
+        assert_eq!(parse_date_input(" Today ", today).unwrap(), today);
+        assert_eq!(parse_date_input("+3d", today).unwrap(), date(10, 17));
+        assert_eq!(parse_date_input("+2w", today).unwrap(), date(10, 28));
+        assert_eq!(
+            parse_date_input("next monday", today).unwrap(),
+            date(10, 19)
+        );
+        assert_eq!(parse_date_input("next wed", today).unwrap(), date(10, 21));
+        assert_eq!(parse_date_input("2026-12-01", today).unwrap(), date(12, 1));
+    }
+
+    #[test]
+    fn parse_date_input_should_reject_malformed_input() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+
+        for input in [
+            "",
+            "soon",
+            "+3",
+            "+d",
+            "+3m",
+            "next",
+            "next week",
+            "2026-13-01",
+        ] {
+            assert!(parse_date_input(input, today).is_err(), "{}", input);
+        }
+        assert_eq!(
+            parse_date_input("soon", today).unwrap_err().to_string(),
+            "invalid date \"soon\": expected YYYY-MM-DD, today, tomorrow, +Nd, +Nw or next <weekday>"
+        );
+    }
+
+    #[test]
+    fn parse_quick_add_should_read_name_and_target_epic() {
+        assert_eq!(
+            parse_quick_add(r#"new "Add 2FA" under EPIC-3"#).unwrap(),
+            QuickAdd {
+                name: "Add 2FA".to_owned(),
+                epic_id: Some(3),
+                assignees: vec![],
+            }
+        );
+        assert_eq!(
+            parse_quick_add(r#""Add 2FA" @alice under 3 @bob"#).unwrap(),
+            QuickAdd {
+                name: "Add 2FA".to_owned(),
+                epic_id: Some(3),
+                assignees: vec!["alice".to_owned(), "bob".to_owned()],
+            }
+        );
+        assert_eq!(
+            parse_quick_add("Fix login crash").unwrap(),
+            QuickAdd {
+                name: "Fix login crash".to_owned(),
+                epic_id: None,
+                assignees: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_quick_add_should_reject_malformed_input() {
+        for input in [
+            r#"new "Add 2FA"#,
+            "new",
+            r#""Add 2FA" under"#,
+            r#""Add 2FA" under STORY-2"#,
+            r#""Add 2FA" under 1 under 2"#,
+            r#""Add 2FA" @"#,
+            r#""Add 2FA" !high"#,
+            r#""Add 2FA" #security"#,
+        ] {
+            assert!(parse_quick_add(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn status_label_should_include_icon_in_emoji_mode() {
+        assert_eq!(Status::Open.label(true), "🟢 OPEN");
+        assert_eq!(Status::InProgress.label(true), "🟡 IN PROGRESS");
+        assert_eq!(Status::Resolved.label(true), "🔵 RESOLVED");
+        assert_eq!(Status::Closed.label(true), "⚫ Closed");
+        assert_eq!(Status::Open.label(false), "OPEN");
+    }
+}
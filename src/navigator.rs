@@ -1,37 +1,70 @@
 use anyhow::{anyhow, Context, Ok, Result};
+use itertools::Itertools;
 use std::{rc::Rc, vec};
 
 use crate::{
     db::JiraDatabase,
-    models::Action,
-    ui::{EpicDetail, HomePage, Page, Prompts, StoryDetail},
+    error::JiraError,
+    models::{parse_quick_add, Action, ItemRef, ResolveResult, SortOrder, Status, Story},
+    ui::{
+        DisplayOptions, EpicDetail, HomePage, Page, Prompts, ReadyQueue, StaleStories,
+        StoriesByStatus, StoryDetail, StoryFocus,
+    },
 };
 
 pub struct Navigator {
     pages: Vec<Box<dyn Page>>,
     prompts: Prompts,
     db: Rc<JiraDatabase>,
+    options: Rc<DisplayOptions>,
 }
 
 impl Navigator {
+    #[cfg(test)]
     pub fn new(db: Rc<JiraDatabase>) -> Self {
+        Self::with_options(db, DisplayOptions::default())
+    }
+
+    pub fn with_options(db: Rc<JiraDatabase>, options: DisplayOptions) -> Self {
+        let options = Rc::new(options);
         Self {
-            pages: vec![Box::new(HomePage { db: db.clone() })],
+            pages: vec![Box::new(HomePage {
+                db: db.clone(),
+                sort: SortOrder::default(),
+                grouped: false,
+                options: options.clone(),
+            })],
             prompts: crate::ui::Prompts::new(),
             db,
+            options,
         }
     }
 
-    pub fn get_current_page(&self) -> Option<&Box<dyn Page>> {
-        self.pages.last()
+    pub fn get_current_page(&self) -> Option<&dyn Page> {
+        self.pages.last().map(|page| page.as_ref())
     }
 
     pub fn handle_action(&mut self, action: Action) -> Result<()> {
+        match self.dispatch(action) {
+            Err(error) if matches!(error.downcast_ref(), Some(JiraError::InputClosed)) => {
+                self.dispatch(Action::Exit)
+            }
+            result => result,
+        }
+    }
+
+    fn dispatch(&mut self, action: Action) -> Result<()> {
+        if self.options.read_only && action.mutates() {
+            return Ok(());
+        }
+
         match action {
             Action::NavigateToEpicDetail { epic_id } => {
                 let epic_detail = EpicDetail {
                     db: self.db.clone(),
                     epic_id,
+                    options: self.options.clone(),
+                    sort: SortOrder::default(),
                 };
 
                 self.pages.push(Box::new(epic_detail));
@@ -42,33 +75,149 @@ impl Navigator {
                     db: self.db.clone(),
                     epic_id,
                     story_id,
+                    options: self.options.clone(),
+                    description_page: 0,
                 };
 
                 self.pages.push(Box::new(story_detail));
             }
 
+            Action::PageStoryDescription {
+                epic_id,
+                story_id,
+                page,
+            } => {
+                self.pages.pop();
+                self.pages.push(Box::new(StoryDetail {
+                    db: self.db.clone(),
+                    epic_id,
+                    story_id,
+                    options: self.options.clone(),
+                    description_page: page,
+                }));
+            }
+
+            Action::NavigateToStoryFocus { story_id } => {
+                let story_focus = StoryFocus {
+                    db: self.db.clone(),
+                    story_id,
+                    options: self.options.clone(),
+                };
+
+                self.pages.push(Box::new(story_focus));
+            }
+
+            Action::NavigateToStoriesByStatus => {
+                let counts = self.db.status_counts()?;
+                if let Some(status) = (self.prompts.pick_status)(&counts)? {
+                    let stories_by_status = StoriesByStatus {
+                        db: self.db.clone(),
+                        status,
+                        options: self.options.clone(),
+                    };
+
+                    self.pages.push(Box::new(stories_by_status));
+                }
+            }
+
+            Action::NavigateToStaleStories => {
+                let stale_stories = StaleStories {
+                    db: self.db.clone(),
+                    options: self.options.clone(),
+                };
+
+                self.pages.push(Box::new(stale_stories));
+            }
+
+            Action::NavigateToReadyQueue => {
+                let ready_queue = ReadyQueue {
+                    db: self.db.clone(),
+                    options: self.options.clone(),
+                };
+
+                self.pages.push(Box::new(ready_queue));
+            }
+
+            Action::SortHomePage { sort } => {
+                let grouped = self.current_home_page().is_some_and(|page| page.grouped);
+                self.replace_home_page(sort, grouped);
+            }
+
+            Action::SortEpicDetail { epic_id, sort } => {
+                self.pages.pop();
+                self.pages.push(Box::new(EpicDetail {
+                    db: self.db.clone(),
+                    epic_id,
+                    options: self.options.clone(),
+                    sort,
+                }));
+            }
+
+            Action::GroupHomePage { grouped } => {
+                let sort = self
+                    .current_home_page()
+                    .map(|page| page.sort)
+                    .unwrap_or_default();
+                self.replace_home_page(sort, grouped);
+            }
+
             Action::NavigateToPreviousPage => {
                 if !self.pages.is_empty() {
                     self.pages.pop();
                 }
+
+                self.db
+                    .flush()
+                    .with_context(|| anyhow!("Failed to save changes"))?;
             }
 
             Action::CreateEpic => {
-                let epic = (self.prompts.create_epic)();
-                self.db.create_epic(epic).with_context(|| anyhow!("Failed to create epic"))?;
+                let epic = (self.prompts.create_epic)()?;
+                self.db
+                    .create_epic(epic)
+                    .with_context(|| anyhow!("Failed to create epic"))?;
             }
 
             Action::UpdateEpicStatus { epic_id } => {
-                let status = (self.prompts.update_status)();
+                let status = (self.prompts.update_status)()?;
 
                 if let Some(status) = status {
+                    let db_state = self.db.read_db()?;
+                    let unchecked = db_state
+                        .epics
+                        .get(&epic_id)
+                        .filter(|epic| status == Status::Closed && epic.status != Status::Closed)
+                        .map(|epic| epic.unchecked_dod())
+                        .unwrap_or_default();
+                    let confirmed = match self.db.closed_stories_reopened_by(epic_id, &status)? {
+                        Some(closed) => (self.prompts.reopen_epic)(closed)?,
+                        None if !unchecked.is_empty() && !self.db.strict_dod => {
+                            (self.prompts.close_unchecked_dod)(&unchecked)?
+                        }
+                        None => true,
+                    };
+                    if confirmed {
+                        self.db
+                            .set_epic_status(epic_id, status, true)
+                            .with_context(|| anyhow!("Failed to update epic"))?;
+                    }
+                }
+            }
+            Action::AddDodItem { epic_id } => {
+                let name = (self.prompts.create_dod_item)()?;
+                self.db
+                    .add_dod_item(epic_id, name)
+                    .with_context(|| anyhow!("Failed to add definition of done item"))?;
+            }
+            Action::ToggleDodItem { epic_id } => {
+                if let Some(index) = (self.prompts.toggle_dod_item)()? {
                     self.db
-                        .update_epic_status(epic_id, status)
-                        .with_context(|| anyhow!("Failed to update epic"))?;
+                        .toggle_dod_item(epic_id, index)
+                        .with_context(|| anyhow!("Failed to toggle definition of done item"))?;
                 }
             }
             Action::DeleteEpic { epic_id } => {
-                if (self.prompts.delete_epic)() {
+                if (self.prompts.delete_epic)()? {
                     self.db
                         .delete_epic(epic_id)
                         .with_context(|| anyhow!("Failed to delete epic"))?;
@@ -78,14 +227,68 @@ impl Navigator {
                     }
                 }
             }
+            Action::DeleteEpicKeepStories { epic_id } => {
+                if (self.prompts.delete_epic_keep_stories)()? {
+                    let reassign_to = (self.prompts.reassign_stories)()?;
+                    self.db
+                        .delete_epic_keep_stories(epic_id, reassign_to)
+                        .with_context(|| anyhow!("Failed to delete epic"))?;
+
+                    if !self.pages.is_empty() {
+                        self.pages.pop();
+                    }
+                }
+            }
+            Action::MergeEpic { epic_id } => {
+                if let Some(dest) = (self.prompts.merge_into)()? {
+                    self.db
+                        .merge_epics(epic_id, dest)
+                        .with_context(|| anyhow!("Failed to merge epic"))?;
+
+                    if !self.pages.is_empty() {
+                        self.pages.pop();
+                    }
+                }
+            }
             Action::CreateStory { epic_id } => {
-                let status = (self.prompts.create_story)();
+                let status = (self.prompts.create_story)()?;
                 self.db
                     .create_story(status, epic_id)
                     .with_context(|| anyhow!("Failed to create story"))?;
             }
+            Action::CreateStoryInPickedEpic => {
+                if let Some(epic_id) = self.pick_epic()? {
+                    self.handle_action(Action::CreateStory { epic_id })?;
+                }
+            }
+            Action::QuickAddStory => {
+                let quick_add = parse_quick_add(&(self.prompts.quick_add)()?)?;
+                let epic_id = match quick_add.epic_id {
+                    Some(epic_id) => Some(epic_id),
+                    None => self.pick_epic()?,
+                };
+
+                if let Some(epic_id) = epic_id {
+                    let mut story = Story::new(quick_add.name, "".to_owned());
+                    for assignee in &quick_add.assignees {
+                        story.add_assignee(assignee);
+                    }
+                    self.db
+                        .create_story(story, epic_id)
+                        .with_context(|| anyhow!("Failed to create story"))?;
+                }
+            }
+            Action::ReplaceText => {
+                if let Some((find, replace, fields, case_sensitive)) =
+                    (self.prompts.replace_text)()?
+                {
+                    self.db
+                        .replace_text(&find, &replace, fields, case_sensitive)
+                        .with_context(|| anyhow!("Failed to replace text"))?;
+                }
+            }
             Action::UpdateStoryStatus { story_id } => {
-                let status = (self.prompts.update_status)();
+                let status = (self.prompts.update_status)()?;
 
                 if let Some(status) = status {
                     self.db
@@ -93,8 +296,104 @@ impl Navigator {
                         .with_context(|| anyhow!("Failed to update story status"))?;
                 }
             }
+            Action::CreateSubtask { story_id } => {
+                let name = (self.prompts.create_subtask)()?;
+                self.db
+                    .add_subtask(story_id, name)
+                    .with_context(|| anyhow!("Failed to create subtask"))?;
+            }
+            Action::ReopenStory { story_id } => {
+                let reason = (self.prompts.reopen_reason)()?;
+                self.db
+                    .reopen_story(story_id, reason)
+                    .with_context(|| anyhow!("Failed to reopen story"))?;
+            }
+            Action::ExtractSubtasks { story_id } => {
+                self.db
+                    .extract_subtasks(story_id)
+                    .with_context(|| anyhow!("Failed to extract subtasks"))?;
+            }
+            Action::ToggleSubtask { story_id } => {
+                let index = (self.prompts.toggle_subtask)()?;
+
+                if let Some(index) = index {
+                    self.db
+                        .toggle_subtask(story_id, index)
+                        .with_context(|| anyhow!("Failed to toggle subtask"))?;
+                }
+            }
+            Action::SnoozeStory { story_id } => {
+                if let Some(until) = (self.prompts.snooze_until)()? {
+                    self.db
+                        .snooze_story(story_id, until)
+                        .with_context(|| anyhow!("Failed to snooze story"))?;
+                }
+            }
+            Action::SetStoryDueDate { story_id } => {
+                if let Some(due_date) = (self.prompts.due_date)()? {
+                    self.db
+                        .set_due_date(story_id, due_date)
+                        .with_context(|| anyhow!("Failed to set due date"))?;
+                }
+            }
+            Action::ToggleEpicStar { epic_id } => {
+                self.db
+                    .toggle_epic_star(epic_id)
+                    .with_context(|| anyhow!("Failed to star epic"))?;
+            }
+            Action::ToggleStoryPin { story_id } => {
+                self.db
+                    .toggle_story_pin(story_id)
+                    .with_context(|| anyhow!("Failed to pin story"))?;
+            }
+            Action::TrackStoryTime { story_id } => {
+                let (estimate_hours, actual_hours) = (self.prompts.track_time)()?;
+                self.db
+                    .track_time(story_id, estimate_hours, actual_hours)
+                    .with_context(|| anyhow!("Failed to track time"))?;
+            }
+            Action::SetStoryPercent { story_id } => {
+                if let Some(percent) = (self.prompts.percent_complete)()? {
+                    self.db
+                        .set_percent_complete(story_id, percent)
+                        .with_context(|| anyhow!("Failed to set percent complete"))?;
+                }
+            }
+            Action::AssignStory { story_id } => {
+                let assignee = (self.prompts.assign)()?;
+                match assignee.strip_prefix('-') {
+                    Some(name) => self.db.remove_assignee(story_id, name),
+                    None => self.db.add_assignee(story_id, &assignee),
+                }
+                .with_context(|| anyhow!("Failed to update assignees"))?;
+            }
+            Action::EditEpic { epic_id } => {
+                let db_state = self.db.read_db()?;
+                let epic = db_state
+                    .epics
+                    .get(&epic_id)
+                    .ok_or_else(|| anyhow!("could not find epic!"))?;
+                let edit = (self.prompts.edit_item)(&epic.name, &epic.description, &epic.status)?;
+
+                self.db
+                    .edit_epic(epic_id, edit)
+                    .with_context(|| anyhow!("Failed to edit epic"))?;
+            }
+            Action::EditStory { story_id } => {
+                let db_state = self.db.read_db()?;
+                let story = db_state
+                    .stories
+                    .get(&story_id)
+                    .ok_or_else(|| anyhow!("could not find story!"))?;
+                let edit =
+                    (self.prompts.edit_item)(&story.name, &story.description, &story.status)?;
+
+                self.db
+                    .edit_story(story_id, edit)
+                    .with_context(|| anyhow!("Failed to edit story"))?;
+            }
             Action::DeleteStory { epic_id, story_id } => {
-                if (self.prompts.delete_story)() {
+                if (self.prompts.delete_story)()? {
                     self.db
                         .delete_story(epic_id, story_id)
                         .with_context(|| anyhow!("Failed to delete story"))?;
@@ -104,19 +403,78 @@ impl Navigator {
                     }
                 }
             }
+            Action::GoTo => {
+                let input = (self.prompts.goto)()?;
+                if input.trim().is_empty() {
+                    return Ok(());
+                }
+
+                match self.db.resolve_reference(&input)? {
+                    ResolveResult::Unique(ItemRef::Story(story_id)) => {
+                        let epic_id = self.db.read_db()?.epic_of(story_id).ok_or_else(|| {
+                            anyhow!("{} is not part of an epic", ItemRef::Story(story_id))
+                        })?;
+
+                        self.handle_action(Action::NavigateToStoryDetail { epic_id, story_id })?;
+                    }
+                    ResolveResult::Unique(item) => {
+                        self.handle_action(Action::NavigateToEpicDetail { epic_id: item.id() })?;
+                    }
+                    ResolveResult::Ambiguous(candidates) => {
+                        return Err(anyhow!(
+                            "\"{}\" matches several items: {}",
+                            input,
+                            candidates.iter().join(", ")
+                        ));
+                    }
+                    ResolveResult::None => return Err(anyhow!("Nothing matches \"{}\"", input)),
+                }
+            }
             Action::Exit => {
                 self.pages.clear();
+
+                self.db
+                    .flush()
+                    .with_context(|| anyhow!("Failed to save changes"))?;
             }
         }
 
         Ok(())
     }
 
+    fn current_home_page(&self) -> Option<&HomePage> {
+        self.get_current_page()?.as_any().downcast_ref::<HomePage>()
+    }
+
+    fn replace_home_page(&mut self, sort: SortOrder, grouped: bool) {
+        self.pages.pop();
+        self.pages.push(Box::new(HomePage {
+            db: self.db.clone(),
+            sort,
+            grouped,
+            options: self.options.clone(),
+        }));
+    }
+
+    fn pick_epic(&self) -> Result<Option<u32>> {
+        let epics = self
+            .db
+            .read_db()?
+            .epics
+            .into_iter()
+            .sorted_by_key(|(epic_id, _)| *epic_id)
+            .map(|(epic_id, epic)| (epic_id, epic.name))
+            .collect_vec();
+
+        (self.prompts.pick_epic)(&epics)
+    }
+
+    #[cfg(test)]
     fn get_page_count(&self) -> usize {
         self.pages.len()
     }
 
-    fn set_prompts(&mut self, prompts: Prompts) {
+    pub fn set_prompts(&mut self, prompts: Prompts) {
         self.prompts = prompts;
     }
 }
@@ -126,14 +484,12 @@ mod tests {
     use super::*;
     use crate::{
         db::test_utils::MockDB,
-        models::{Epic, Status, Story},
+        models::{Epic, ItemEdit, Status, Story},
     };
 
     #[test]
     fn should_start_on_home_page() {
-        let db = Rc::new(JiraDatabase {
-            database: Box::new(MockDB::new()),
-        });
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
         let nav = Navigator::new(db);
 
         assert_eq!(nav.get_page_count(), 1);
@@ -146,9 +502,7 @@ mod tests {
 
     #[test]
     fn handle_action_should_navigate_pages() {
-        let db = Rc::new(JiraDatabase {
-            database: Box::new(MockDB::new()),
-        });
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
         let mut nav = Navigator::new(db);
 
@@ -193,11 +547,190 @@ mod tests {
     }
 
     #[test]
-    fn handle_action_should_clear_pages_on_exit() {
-        let db = Rc::new(JiraDatabase {
-            database: Box::new(MockDB::new()),
+    fn handle_action_should_skip_changes_in_read_only_mode() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let mut nav = Navigator::with_options(
+            db.clone(),
+            DisplayOptions {
+                read_only: true,
+                ..DisplayOptions::default()
+            },
+        );
+        let mut prompts = Prompts::new();
+        prompts.create_epic = Box::new(|| panic!("create prompt shown in read-only mode"));
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::CreateEpic).unwrap();
+        nav.handle_action(Action::NavigateToStaleStories).unwrap();
+
+        assert!(db.read_db().unwrap().epics.is_empty());
+        assert_eq!(nav.get_page_count(), 2);
+    }
+
+    #[test]
+    fn handle_action_should_sort_epic_detail_in_place() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::new(db);
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+            .unwrap();
+
+        let sort = SortOrder::default().next();
+        nav.handle_action(Action::SortEpicDetail { epic_id: 1, sort })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let epic_detail_page = current_page.as_any().downcast_ref::<EpicDetail>().unwrap();
+        assert_eq!(epic_detail_page.epic_id, 1);
+        assert_eq!(epic_detail_page.sort, sort);
+
+        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
+        assert_eq!(nav.current_home_page().unwrap().sort, SortOrder::default());
+    }
+
+    #[test]
+    fn handle_action_should_page_story_description_in_place() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::new(db);
+        nav.handle_action(Action::NavigateToStoryDetail {
+            epic_id: 1,
+            story_id: 2,
+        })
+        .unwrap();
+        nav.handle_action(Action::PageStoryDescription {
+            epic_id: 1,
+            story_id: 2,
+            page: 1,
+        })
+        .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let story_detail_page = current_page.as_any().downcast_ref::<StoryDetail>().unwrap();
+        assert_eq!(story_detail_page.story_id, 2);
+        assert_eq!(story_detail_page.description_page, 1);
+    }
+
+    #[test]
+    fn handle_action_should_open_and_leave_story_focus() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::new(db);
+
+        nav.handle_action(Action::NavigateToStoryFocus { story_id: 2 })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let story_focus_page = current_page.as_any().downcast_ref::<StoryFocus>();
+        assert!(story_focus_page.is_some());
+
+        nav.handle_action(Action::NavigateToPreviousPage).unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+    }
+
+    #[test]
+    fn handle_action_should_navigate_to_stories_by_status() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::new(db);
+
+        let mut prompts = Prompts::new();
+        prompts.pick_status = Box::new(|counts| {
+            assert_eq!(counts.len(), 4);
+            Ok(Some(Status::InProgress))
         });
 
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToStoriesByStatus)
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let stories_by_status = current_page
+            .as_any()
+            .downcast_ref::<StoriesByStatus>()
+            .unwrap();
+        assert_eq!(stories_by_status.status, Status::InProgress);
+    }
+
+    #[test]
+    fn handle_action_should_go_to_resolved_reference() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic(Epic::new("Login flow".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("Logout".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(db);
+
+        let mut prompts = Prompts::new();
+        prompts.goto = Box::new(|| Ok("logout".to_owned()));
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::GoTo).unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+
+        let current_page = nav.get_current_page().unwrap();
+        let story_detail = current_page.as_any().downcast_ref::<StoryDetail>().unwrap();
+        assert_eq!(story_detail.epic_id, epic_id);
+        assert_eq!(story_detail.story_id, story_id);
+
+        let mut prompts = Prompts::new();
+        prompts.goto = Box::new(|| Ok("log".to_owned()));
+        nav.set_prompts(prompts);
+
+        assert!(nav.handle_action(Action::GoTo).is_err());
+        assert_eq!(nav.get_page_count(), 2);
+    }
+
+    #[test]
+    fn handle_action_should_replace_home_page_on_sort() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::new(db);
+        let sort = SortOrder::default().next();
+
+        nav.handle_action(Action::SortHomePage { sort }).unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+
+        let current_page = nav.get_current_page().unwrap();
+        let home_page = current_page.as_any().downcast_ref::<HomePage>().unwrap();
+        assert_eq!(home_page.sort, sort);
+    }
+
+    #[test]
+    fn handle_action_should_keep_sort_when_grouping_home_page() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::new(db);
+        let sort = SortOrder::default().next();
+
+        nav.handle_action(Action::SortHomePage { sort }).unwrap();
+        nav.handle_action(Action::GroupHomePage { grouped: true })
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+
+        let home_page = nav.current_home_page().unwrap();
+        assert!(home_page.grouped);
+        assert_eq!(home_page.sort, sort);
+
+        nav.handle_action(Action::SortHomePage {
+            sort: SortOrder::default(),
+        })
+        .unwrap();
+        assert!(nav.current_home_page().unwrap().grouped);
+    }
+
+    #[test]
+    fn handle_action_should_clear_pages_on_exit() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
         let mut nav = Navigator::new(db);
 
         nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
@@ -212,16 +745,32 @@ mod tests {
         assert_eq!(nav.get_page_count(), 0);
     }
 
+    #[test]
+    fn handle_action_should_exit_when_a_prompt_is_interrupted() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
+        let mut nav = Navigator::new(db);
+
+        let mut prompts = Prompts::new();
+        prompts.create_epic = Box::new(|| Err(JiraError::InputClosed.into()));
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+            .unwrap();
+        nav.handle_action(Action::CreateEpic).unwrap();
+
+        assert_eq!(nav.get_page_count(), 0);
+    }
+
     #[test]
     fn handle_action_should_handle_create_epic() {
-        let db = Rc::new(JiraDatabase {
-            database: Box::new(MockDB::new()),
-        });
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.create_epic = Box::new(|| Epic::new("name".to_owned(), "description".to_owned()));
+        prompts.create_epic =
+            Box::new(|| Ok(Epic::new("name".to_owned(), "description".to_owned())));
 
         nav.set_prompts(prompts);
 
@@ -237,9 +786,7 @@ mod tests {
 
     #[test]
     fn handle_action_should_handle_update_epic() {
-        let db = Rc::new(JiraDatabase {
-            database: Box::new(MockDB::new()),
-        });
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
             .unwrap();
@@ -247,7 +794,7 @@ mod tests {
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.update_status = Box::new(|| Some(Status::InProgress));
+        prompts.update_status = Box::new(|| Ok(Some(Status::InProgress)));
 
         nav.set_prompts(prompts);
 
@@ -262,10 +809,65 @@ mod tests {
     }
 
     #[test]
-    fn handle_action_should_handle_delete_epic() {
-        let db = Rc::new(JiraDatabase {
-            database: Box::new(MockDB::new()),
+    fn handle_action_should_confirm_reopening_a_closed_epic() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        db.update_epic_status(epic_id, Status::Closed).unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_status = Box::new(|| Ok(Some(Status::Open)));
+        prompts.reopen_epic = Box::new(|_| Ok(false));
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateEpicStatus { epic_id })
+            .unwrap();
+        assert_eq!(db.read_db().unwrap().epics[&epic_id].status, Status::Closed);
+
+        let mut prompts = Prompts::new();
+        prompts.update_status = Box::new(|| Ok(Some(Status::Open)));
+        prompts.reopen_epic = Box::new(|_| Ok(true));
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateEpicStatus { epic_id })
+            .unwrap();
+        assert_eq!(db.read_db().unwrap().epics[&epic_id].status, Status::Open);
+    }
+
+    #[test]
+    fn handle_action_should_return_an_unreadable_date() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.snooze_until = Box::new(|| {
+            crate::models::parse_date_input("someday", chrono::NaiveDate::MIN).map(Some)
         });
+        prompts.due_date = Box::new(|| Err(anyhow!("could not read date")));
+        nav.set_prompts(prompts);
+
+        assert!(nav.handle_action(Action::SnoozeStory { story_id }).is_err());
+        assert!(nav
+            .handle_action(Action::SetStoryDueDate { story_id })
+            .is_err());
+        let story = &db.read_db().unwrap().stories[&story_id];
+        assert_eq!(story.snoozed_until, None);
+        assert_eq!(story.due_date, None);
+    }
+
+    #[test]
+    fn handle_action_should_handle_delete_epic() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
             .unwrap();
@@ -273,7 +875,7 @@ mod tests {
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.delete_epic = Box::new(|| true);
+        prompts.delete_epic = Box::new(|| Ok(true));
 
         nav.set_prompts(prompts);
 
@@ -283,11 +885,72 @@ mod tests {
         assert_eq!(db_state.epics.len(), 0);
     }
 
+    #[test]
+    fn handle_action_should_handle_delete_epic_keep_stories() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let target_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.delete_epic_keep_stories = Box::new(|| Ok(true));
+        prompts.reassign_stories = Box::new(move || Ok(Some(target_id)));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::DeleteEpicKeepStories { epic_id })
+            .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics.len(), 1);
+        assert_eq!(db_state.stories.len(), 1);
+        assert_eq!(
+            db_state.epics.get(&target_id).unwrap().stories,
+            vec![story_id]
+        );
+    }
+
+    #[test]
+    fn handle_action_should_handle_merge_epic() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let dest = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.merge_into = Box::new(move || Ok(Some(dest)));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        nav.handle_action(Action::MergeEpic { epic_id }).unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.epics.len(), 1);
+        assert_eq!(db_state.epics.get(&dest).unwrap().stories, vec![story_id]);
+    }
+
     #[test]
     fn handle_action_should_handle_create_story() {
-        let db = Rc::new(JiraDatabase {
-            database: Box::new(MockDB::new()),
-        });
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
             .unwrap();
@@ -295,7 +958,8 @@ mod tests {
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.create_story = Box::new(|| Story::new("name".to_owned(), "description".to_owned()));
+        prompts.create_story =
+            Box::new(|| Ok(Story::new("name".to_owned(), "description".to_owned())));
 
         nav.set_prompts(prompts);
 
@@ -310,10 +974,36 @@ mod tests {
     }
 
     #[test]
-    fn handle_action_should_handle_update_story() {
-        let db = Rc::new(JiraDatabase {
-            database: Box::new(MockDB::new()),
+    fn handle_action_should_create_story_in_picked_epic() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let _ = db
+            .create_epic(Epic::new("first".to_owned(), "".to_owned()))
+            .unwrap();
+        let second = db
+            .create_epic(Epic::new("second".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.pick_epic = Box::new(|epics| {
+            assert_eq!(epics.len(), 2);
+            Ok(Some(epics[1].0))
         });
+        prompts.create_story = Box::new(|| Ok(Story::new("story".to_owned(), "".to_owned())));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::CreateStoryInPickedEpic).unwrap();
+
+        let db_state = db.read_db().unwrap();
+        assert_eq!(db_state.stories.len(), 1);
+        assert_eq!(db_state.epics.get(&second).unwrap().stories.len(), 1);
+    }
+
+    #[test]
+    fn handle_action_should_handle_update_story() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
             .unwrap();
@@ -324,7 +1014,7 @@ mod tests {
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.update_status = Box::new(|| Some(Status::InProgress));
+        prompts.update_status = Box::new(|| Ok(Some(Status::InProgress)));
 
         nav.set_prompts(prompts);
 
@@ -339,10 +1029,85 @@ mod tests {
     }
 
     #[test]
-    fn handle_action_should_handle_delete_story() {
-        let db = Rc::new(JiraDatabase {
-            database: Box::new(MockDB::new()),
+    fn handle_action_should_handle_subtasks() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.create_subtask = Box::new(|| Ok("subtask".to_owned()));
+        prompts.toggle_subtask = Box::new(|| Ok(Some(0)));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::CreateSubtask { story_id })
+            .unwrap();
+        nav.handle_action(Action::ToggleSubtask { story_id })
+            .unwrap();
+
+        let db_state = db.read_db().unwrap();
+        let story = db_state.stories.get(&story_id).unwrap();
+        assert_eq!(story.subtasks.len(), 1);
+        assert!(story.subtasks[0].done);
+    }
+
+    #[test]
+    fn handle_action_should_toggle_epic_star() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        nav.handle_action(Action::ToggleEpicStar { epic_id })
+            .unwrap();
+
+        assert!(db.read_db().unwrap().epics[&epic_id].starred);
+    }
+
+    #[test]
+    fn handle_action_should_handle_edit_story() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+        let epic_id = db
+            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(
+                Story::new("name".to_owned(), "description".to_owned()),
+                epic_id,
+            )
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.edit_item = Box::new(|_, _, _| {
+            Ok(ItemEdit {
+                status: Some(Status::Resolved),
+                ..Default::default()
+            })
         });
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::EditStory { story_id }).unwrap();
+
+        let db_state = db.read_db().unwrap();
+        let story = db_state.stories.get(&story_id).unwrap();
+        assert_eq!(story.name, "name");
+        assert_eq!(story.description, "description");
+        assert_eq!(story.status, Status::Resolved);
+    }
+
+    #[test]
+    fn handle_action_should_handle_delete_story() {
+        let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
         let epic_id = db
             .create_epic(Epic::new("".to_owned(), "".to_owned()))
             .unwrap();
@@ -353,7 +1118,7 @@ mod tests {
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.delete_story = Box::new(|| true);
+        prompts.delete_story = Box::new(|| Ok(true));
 
         nav.set_prompts(prompts);
 
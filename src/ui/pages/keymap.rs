@@ -0,0 +1,125 @@
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+const FIXED_KEYS: [&str; 20] = [
+    "q", "s", "i", "w", "g", "n", "a", "r", "o", "l", "*", "k", "m", "t", "x", "z", "f", "h", "y",
+    "%",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Keymap {
+    pub back: String,
+    pub create: String,
+    pub update: String,
+    pub edit: String,
+    pub delete: String,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            back: "b".to_owned(),
+            create: "c".to_owned(),
+            update: "u".to_owned(),
+            edit: "e".to_owned(),
+            delete: "d".to_owned(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn validate(&self) -> Result<()> {
+        let bindings = [
+            ("back", &self.back),
+            ("create", &self.create),
+            ("update", &self.update),
+            ("edit", &self.edit),
+            ("delete", &self.delete),
+        ];
+
+        if let Some((name, key)) = bindings.iter().find(|(_, key)| key.chars().count() != 1) {
+            return Err(anyhow!(
+                "key for {} must be a single character, got '{}'",
+                name,
+                key
+            ));
+        }
+        if let Some((name, key)) = bindings
+            .iter()
+            .find(|(_, key)| key.chars().all(|c| c.is_ascii_digit()))
+        {
+            return Err(anyhow!(
+                "key for {} cannot be the digit '{}', digits select items",
+                name,
+                key
+            ));
+        }
+        if let Some((name, key)) = bindings
+            .iter()
+            .find(|(_, key)| FIXED_KEYS.contains(&key.as_str()))
+        {
+            return Err(anyhow!(
+                "key for {} cannot be '{}', it is already a shortcut",
+                name,
+                key
+            ));
+        }
+        if let Some(key) = bindings.iter().map(|(_, key)| key).duplicates().next() {
+            return Err(anyhow!("key '{}' is bound more than once", key));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_should_accept_default_bindings() {
+        assert!(Keymap::default().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_should_reject_long_or_duplicate_keys() {
+        let long = Keymap {
+            back: "back".to_owned(),
+            ..Keymap::default()
+        };
+        let duplicate = Keymap {
+            delete: "c".to_owned(),
+            ..Keymap::default()
+        };
+
+        assert!(long.validate().is_err());
+        assert!(duplicate.validate().is_err());
+    }
+
+    #[test]
+    fn validate_should_reject_digits() {
+        let digit = Keymap {
+            back: "1".to_owned(),
+            ..Keymap::default()
+        };
+
+        assert!(digit.validate().is_err());
+    }
+
+    #[test]
+    fn validate_should_reject_fixed_shortcuts() {
+        let quit = Keymap {
+            create: "q".to_owned(),
+            ..Keymap::default()
+        };
+        let reopen = Keymap {
+            edit: "r".to_owned(),
+            ..Keymap::default()
+        };
+
+        assert!(quit.validate().is_err());
+        assert!(reopen.validate().is_err());
+    }
+}
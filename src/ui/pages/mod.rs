@@ -1,44 +1,202 @@
-use std::rc::Rc;
+use std::{collections::HashMap, io::Write, rc::Rc};
 
 use anyhow::anyhow;
 use anyhow::Result;
+use chrono::{Local, NaiveDate, Utc};
 use itertools::Itertools;
 use std::any::Any;
 
 use crate::db::JiraDatabase;
-use crate::models::Action;
+use crate::io_utils::{terminal_height, terminal_width};
+use crate::models::{parse_item_ref, Action, Epic, ItemRef, SortOrder, Status, Story};
 
+mod keymap;
 mod page_helpers;
 
+pub use keymap::Keymap;
+
+use page_helpers::Table;
+
 pub trait Page {
-    fn draw_page(&self) -> Result<()>;
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()>;
     fn handle_input(&self, input: &str) -> Result<Option<Action>>;
     fn as_any(&self) -> &dyn Any;
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DisplayOptions {
+    pub emoji: bool,
+    pub ansi: bool,
+    pub page_size: Option<usize>,
+    pub show_history: bool,
+    pub show_snoozed: bool,
+    pub stale_days: u32,
+    pub progress_width: Option<usize>,
+    pub read_only: bool,
+    pub keys: Keymap,
+}
+
+pub fn supports_utf8(locale: &str) -> bool {
+    let locale = locale.to_ascii_lowercase();
+    locale.contains("utf-8") || locale.contains("utf8")
+}
+
+fn print_table(out: &mut dyn Write, table: &Table) -> Result<()> {
+    for line in table.render() {
+        writeln!(out, "{}", line)?;
+    }
+
+    Ok(())
+}
+
+fn visible_stories<'a>(
+    story_ids: &'a [u32],
+    stories: &'a HashMap<u32, Story>,
+    today: NaiveDate,
+    show_snoozed: bool,
+    sort: SortOrder,
+) -> Vec<(&'a u32, &'a Story)> {
+    story_ids
+        .iter()
+        .filter_map(|story_id| stories.get(story_id).map(|story| (story_id, story)))
+        .filter(|(_, story)| show_snoozed || !story.is_snoozed(today))
+        .sorted_by(|a, b| {
+            b.1.pinned
+                .cmp(&a.1.pinned)
+                .then_with(|| sort.compare(*a, *b))
+        })
+        .collect()
+}
+
+const DEFAULT_PROGRESS_WIDTH: usize = 10;
+const HOME_TABLE_WIDTH: usize = 70;
+const STORY_DETAIL_CHROME_LINES: usize = 8;
+const DESCRIPTION_WIDTH: usize = 66;
+
 pub struct HomePage {
     pub db: Rc<JiraDatabase>,
+    pub sort: SortOrder,
+    pub grouped: bool,
+    pub options: Rc<DisplayOptions>,
 }
 
-impl Page for HomePage {
-    fn draw_page(&self) -> Result<()> {
-        let epics = self.db.read_db()?.epics;
-        for (key, value) in epics.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
-            let epic_status = value.status.to_string();
-            let key_string = key.to_string();
+impl HomePage {
+    fn ordered_epics<'a>(&self, epics: &'a HashMap<u32, Epic>) -> Vec<(&'a u32, &'a Epic)> {
+        epics
+            .iter()
+            .sorted_by(|a, b| {
+                b.1.starred
+                    .cmp(&a.1.starred)
+                    .then_with(|| self.sort.compare(*a, *b))
+            })
+            .collect()
+    }
 
-            println!("----------------------------- EPICS -----------------------------");
-            println!("     id     |               name               |      status      ");
-            println!(
-                "{} | {} | {}",
-                page_helpers::get_column_string(&key_string, 11),
-                page_helpers::get_column_string(&value.name, 32),
-                page_helpers::get_column_string(&epic_status, 16)
-            );
-            println!();
-            println!();
+    fn print_epic_rows(&self, out: &mut dyn Write, epics: Vec<(&u32, &Epic)>) -> Result<()> {
+        let bar_width = self
+            .options
+            .progress_width
+            .unwrap_or(DEFAULT_PROGRESS_WIDTH)
+            .min(terminal_width().saturating_sub(HOME_TABLE_WIDTH + 5));
+        let mut table = Table::new(&[
+            ("id", 11),
+            ("name", 34),
+            ("status", 16),
+            ("progress", bar_width + 2),
+        ]);
+        for (key, value) in epics {
+            let star = if value.starred { "★" } else { "☆" };
+            let percent = self.db.epic_percent_complete(*key)?;
+            table.add_row(vec![
+                ItemRef::Epic(*key).to_string(),
+                format!("{} {}", star, value.name),
+                value.status.label(self.options.emoji),
+                page_helpers::progress_bar(percent.into(), 100, bar_width),
+            ]);
         }
-        println!("[q] quit | [c] create epic | [:id:] navigate to epic");
+        print_table(out, &table)?;
+
+        Ok(())
+    }
+}
+
+fn group_epics_by_status(epics: &HashMap<u32, Epic>) -> Vec<(Status, Vec<(&u32, &Epic)>)> {
+    [
+        Status::Open,
+        Status::InProgress,
+        Status::Resolved,
+        Status::Closed,
+    ]
+    .into_iter()
+    .map(|status| {
+        let group = epics
+            .iter()
+            .filter(|(_, epic)| epic.status == status)
+            .sorted_by_key(|(id, _)| **id)
+            .collect_vec();
+        (status, group)
+    })
+    .filter(|(_, group)| !group.is_empty())
+    .collect()
+}
+
+impl Page for HomePage {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+        if let Some(label) = db_state.project_label() {
+            writeln!(out, "{}", label)?;
+        }
+        let epics = db_state.epics;
+
+        writeln!(
+            out,
+            "----------------------------- EPICS -----------------------------"
+        )?;
+        if epics.is_empty() && self.options.read_only {
+            writeln!(out, "No epics yet.")?;
+        } else if epics.is_empty() {
+            writeln!(
+                out,
+                "No epics yet — press '{}' to create one.",
+                self.options.keys.create
+            )?;
+        } else if self.grouped {
+            for (status, group) in group_epics_by_status(&epics) {
+                writeln!(out)?;
+                writeln!(
+                    out,
+                    "{} ({})",
+                    status.label(self.options.emoji),
+                    group.len()
+                )?;
+                self.print_epic_rows(out, group)?;
+            }
+        } else {
+            self.print_epic_rows(out, self.ordered_epics(&epics))?;
+        }
+        writeln!(out)?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "Board completion: {:.0}%",
+            self.db.completion_ratio()? * 100.0
+        )?;
+        if !self.grouped {
+            writeln!(out, "Sorted by: {}", self.sort)?;
+        }
+        writeln!(out)?;
+        if self.options.read_only {
+            writeln!(
+                out,
+                "[q] quit | [s] stories by status | [i] idle stories | [w] ready to start | [o] change sort | [l] toggle grouping | [g] go to | [:id:] navigate to epic"
+            )?;
+            return Ok(());
+        }
+        writeln!(
+            out,
+            "[q] quit | [{}] create epic | [n] new story | [a] quick add story | [r] find and replace | [s] stories by status | [i] idle stories | [w] ready to start | [o] change sort | [l] toggle grouping | [g] go to | [:id:] navigate to epic",
+            self.options.keys.create
+        )?;
 
         Ok(())
     }
@@ -48,19 +206,34 @@ impl Page for HomePage {
     }
 
     fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        let keys = &self.options.keys;
+
         match input {
+            key if key == keys.create => Ok(Some(Action::CreateEpic)),
             "q" => Ok(Some(Action::Exit)),
-            "c" => Ok(Some(Action::CreateEpic)),
+            "s" => Ok(Some(Action::NavigateToStoriesByStatus)),
+            "i" => Ok(Some(Action::NavigateToStaleStories)),
+            "w" => Ok(Some(Action::NavigateToReadyQueue)),
+            "g" => Ok(Some(Action::GoTo)),
+            "n" => Ok(Some(Action::CreateStoryInPickedEpic)),
+            "a" => Ok(Some(Action::QuickAddStory)),
+            "r" => Ok(Some(Action::ReplaceText)),
+            "o" => Ok(Some(Action::SortHomePage {
+                sort: self.sort.next(),
+            })),
+            "l" => Ok(Some(Action::GroupHomePage {
+                grouped: !self.grouped,
+            })),
             "" => Ok(None),
-            input => match input.parse::<u32>() {
-                Err(_) => Ok(None),
-                Ok(id) => {
+            input => match parse_item_ref(input) {
+                Some(ItemRef::Epic(id)) | Some(ItemRef::Any(id)) => {
                     if self.db.read_db()?.epics.contains_key(&id) {
                         return Ok(Some(Action::NavigateToEpicDetail { epic_id: id }));
                     }
 
                     Ok(None)
                 }
+                _ => Ok(None),
             },
         }
     }
@@ -69,42 +242,158 @@ impl Page for HomePage {
 pub struct EpicDetail {
     pub epic_id: u32,
     pub db: Rc<JiraDatabase>,
+    pub options: Rc<DisplayOptions>,
+    pub sort: SortOrder,
 }
 
 impl Page for EpicDetail {
-    fn draw_page(&self) -> Result<()> {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
         let db_state = self.db.read_db()?;
         let epic = db_state
             .epics
             .get(&self.epic_id)
             .ok_or_else(|| anyhow!("could not find epic!"))?;
 
-        println!("------------------------------ EPIC ------------------------------");
-        println!("  id  |     name     |         description         |    status    ");
-        println!(
-            "{0: <5} | {1: <12} | {2: <27} | {3: <13}",
-            self.epic_id,
+        writeln!(
+            out,
+            "------------------------------ EPIC ------------------------------"
+        )?;
+        writeln!(
+            out,
+            "     id     |     name     |      description      |    status    "
+        )?;
+        writeln!(
+            out,
+            "{0: <11} | {1: <12} | {2: <21} | {3: <13}",
+            ItemRef::Epic(self.epic_id).to_string(),
             page_helpers::get_column_string(&epic.name, 9),
-            page_helpers::get_column_string(&epic.description, 24),
-            epic.status
-        );
-        println!();
-
-        let stories = &db_state.stories;
-        println!("---------------------------- STORIES ----------------------------");
-        println!("     id     |               name               |      status      ");
-        for (key, value) in stories.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
-            println!(
-                "{0: <11} | {1: <32} | {2: <17}",
-                key,
-                page_helpers::get_column_string(&value.name, 28),
-                value.status
-            );
+            page_helpers::get_column_string(&epic.description, 21),
+            epic.status.label(self.options.emoji)
+        )?;
+        writeln!(
+            out,
+            "{}",
+            page_helpers::status_breakdown(
+                epic.stories
+                    .iter()
+                    .filter_map(|story_id| db_state.stories.get(story_id))
+                    .map(|story| &story.status)
+            )
+        )?;
+        if !epic.description.is_empty() {
+            writeln!(out)?;
+            writeln!(
+                out,
+                "{}",
+                page_helpers::wrap_text(&epic.description, DESCRIPTION_WIDTH, 2)
+            )?;
+            writeln!(out)?;
+        }
+        let (done, total) = self.db.epic_progress(self.epic_id)?;
+        writeln!(
+            out,
+            "{} of {} stories done ({}% complete)",
+            done,
+            total,
+            self.db.epic_percent_complete(self.epic_id)?
+        )?;
+        let unestimated = epic
+            .stories
+            .iter()
+            .filter_map(|story_id| db_state.stories.get(story_id))
+            .filter(|story| story.estimate_hours.is_none())
+            .count();
+        if unestimated < total {
+            let (estimate, actual) = self.db.time_rollup(self.epic_id)?;
+            writeln!(
+                out,
+                "Time: {:.1}h estimated, {:.1}h spent ({} unestimated)",
+                estimate, actual, unestimated
+            )?;
+            writeln!(
+                out,
+                "Critical path: {}",
+                self.db
+                    .critical_path(self.epic_id)?
+                    .into_iter()
+                    .map(ItemRef::Story)
+                    .join(" -> ")
+            )?;
+        }
+        match self.db.next_actionable_story(self.epic_id)? {
+            Some(story_id) => writeln!(
+                out,
+                "Next up: {} {}",
+                ItemRef::Story(story_id),
+                db_state.stories[&story_id].name
+            )?,
+            None => writeln!(out, "Next up: all stories in progress or done")?,
+        }
+        writeln!(out)?;
+
+        if !epic.dod.is_empty() {
+            writeln!(
+                out,
+                "------------------------ DEFINITION OF DONE ------------------------"
+            )?;
+            for (index, (name, checked)) in epic.dod.iter().enumerate() {
+                let mark = if *checked { "x" } else { " " };
+                writeln!(out, "{0: >3}. [{1}] {2}", index + 1, mark, name)?;
+            }
+            writeln!(
+                out,
+                "{} of {} definition of done items checked",
+                epic.dod.len() - epic.unchecked_dod().len(),
+                epic.dod.len()
+            )?;
+            writeln!(out)?;
         }
-        println!();
-        println!();
 
-        println!("[p] previous | [u] update epic | [d] delete epic | [c] create story | [:id:] navigate to story");
+        let today = Local::now().date_naive();
+        writeln!(
+            out,
+            "---------------------------- STORIES ----------------------------"
+        )?;
+        let mut table = Table::new(&[("id", 11), ("name", 32), ("status", 16)]);
+        for (key, value) in visible_stories(
+            &epic.stories,
+            &db_state.stories,
+            today,
+            self.options.show_snoozed,
+            self.sort,
+        ) {
+            let row = vec![
+                ItemRef::Story(*key).to_string(),
+                match value.pinned {
+                    true => format!("* {}", value.name),
+                    false => value.name.clone(),
+                },
+                value.status.label(self.options.emoji),
+            ];
+            match self.options.ansi && value.is_snoozed(today) {
+                true => table.add_dimmed_row(row),
+                false => table.add_row(row),
+            }
+        }
+        print_table(out, &table)?;
+        writeln!(out)?;
+        writeln!(out, "Sorted by: {}", self.sort)?;
+        writeln!(out)?;
+
+        let keys = &self.options.keys;
+        if self.options.read_only {
+            writeln!(
+                out,
+                "[{}] previous | [o] change sort | [:id:] navigate to story",
+                keys.back
+            )?;
+            return Ok(());
+        }
+        writeln!(
+            out,
+            "[{}] previous | [{}] update epic | [{}] edit epic | [{}] delete epic | [k] delete epic, keep stories | [m] merge into epic | [*] star epic | [a] add done item | [t] toggle done item | [{}] create story | [o] change sort | [:id:] navigate to story",
+            keys.back, keys.update, keys.edit, keys.delete, keys.create
+        )?;
 
         Ok(())
     }
@@ -114,21 +403,44 @@ impl Page for EpicDetail {
     }
 
     fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        let keys = &self.options.keys;
+
         match input {
-            "p" => Ok(Some(Action::NavigateToPreviousPage)),
-            "u" => Ok(Some(Action::UpdateEpicStatus {
+            key if key == keys.back => Ok(Some(Action::NavigateToPreviousPage)),
+            key if key == keys.update => Ok(Some(Action::UpdateEpicStatus {
+                epic_id: self.epic_id,
+            })),
+            key if key == keys.delete => Ok(Some(Action::DeleteEpic {
+                epic_id: self.epic_id,
+            })),
+            key if key == keys.edit => Ok(Some(Action::EditEpic {
+                epic_id: self.epic_id,
+            })),
+            key if key == keys.create => Ok(Some(Action::CreateStory {
+                epic_id: self.epic_id,
+            })),
+            "k" => Ok(Some(Action::DeleteEpicKeepStories {
+                epic_id: self.epic_id,
+            })),
+            "o" => Ok(Some(Action::SortEpicDetail {
+                epic_id: self.epic_id,
+                sort: self.sort.next(),
+            })),
+            "m" => Ok(Some(Action::MergeEpic {
                 epic_id: self.epic_id,
             })),
-            "d" => Ok(Some(Action::DeleteEpic {
+            "*" => Ok(Some(Action::ToggleEpicStar {
                 epic_id: self.epic_id,
             })),
-            "c" => Ok(Some(Action::CreateStory {
+            "a" => Ok(Some(Action::AddDodItem {
+                epic_id: self.epic_id,
+            })),
+            "t" => Ok(Some(Action::ToggleDodItem {
                 epic_id: self.epic_id,
             })),
             "" => Ok(None),
-            input => match input.parse::<u32>() {
-                Err(_) => Ok(None),
-                Ok(id) => {
+            input => match parse_item_ref(input) {
+                Some(ItemRef::Story(id)) | Some(ItemRef::Any(id)) => {
                     if self.db.read_db()?.stories.contains_key(&id) {
                         return Ok(Some(Action::NavigateToStoryDetail {
                             epic_id: self.epic_id,
@@ -138,6 +450,7 @@ impl Page for EpicDetail {
 
                     Ok(None)
                 }
+                _ => Ok(None),
             },
         }
     }
@@ -147,29 +460,135 @@ pub struct StoryDetail {
     pub epic_id: u32,
     pub story_id: u32,
     pub db: Rc<JiraDatabase>,
+    pub options: Rc<DisplayOptions>,
+    pub description_page: usize,
+}
+
+impl StoryDetail {
+    fn description_pages(&self, story: &Story) -> Vec<String> {
+        page_helpers::paginate_text(
+            &page_helpers::wrap_text(&story.description, DESCRIPTION_WIDTH, 2),
+            self.options
+                .page_size
+                .unwrap_or_else(|| terminal_height().saturating_sub(STORY_DETAIL_CHROME_LINES)),
+        )
+    }
 }
 
 impl Page for StoryDetail {
-    fn draw_page(&self) -> Result<()> {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
         let db_state = self.db.read_db()?;
         let story = db_state
             .stories
             .get(&self.story_id)
             .ok_or_else(|| anyhow!("could not find story!"))?;
 
-        println!("------------------------------ STORY ------------------------------");
-        println!("  id  |     name     |         description         |    status    ");
-        println!(
-            "{0: <5} | {1: <13} | {2: <28} |  {3: <13}",
-            self.story_id,
+        writeln!(
+            out,
+            "------------------------------ STORY ------------------------------"
+        )?;
+        writeln!(
+            out,
+            "     id     |     name      |      description       |    status    "
+        )?;
+        writeln!(
+            out,
+            "{0: <11} | {1: <13} | {2: <22} |  {3: <13}",
+            ItemRef::Story(self.story_id).to_string(),
             page_helpers::get_column_string(&story.name, 13),
-            page_helpers::get_column_string(&story.description, 28),
-            story.status
-        );
-        println!();
-        println!();
+            page_helpers::get_column_string(&story.description, 22),
+            story.status.label(self.options.emoji)
+        )?;
+        writeln!(out)?;
+
+        let pages = self.description_pages(story);
+        if let Some(page) = pages.get(self.description_page) {
+            writeln!(out, "{}", page)?;
+            if self.description_page + 1 < pages.len() {
+                writeln!(
+                    out,
+                    "-- page {} of {}, press enter for more --",
+                    self.description_page + 1,
+                    pages.len()
+                )?;
+            } else if pages.len() > 1 {
+                writeln!(out, "-- page {} of {} --", pages.len(), pages.len())?;
+            }
+            writeln!(out)?;
+        }
 
-        println!("[p] previous | [u] update story | [d] delete story");
+        if !story.subtasks.is_empty() {
+            writeln!(
+                out,
+                "---------------------------- SUBTASKS ----------------------------"
+            )?;
+            for (index, subtask) in story.subtasks.iter().enumerate() {
+                let mark = if subtask.done { "x" } else { " " };
+                writeln!(out, "{0: >3}. [{1}] {2}", index + 1, mark, subtask.name)?;
+            }
+            writeln!(
+                out,
+                "{} of {} subtasks done",
+                story.completed_subtasks(),
+                story.subtasks.len()
+            )?;
+        }
+        if story.percent_complete > 0 {
+            writeln!(out, "Progress: {}%", story.percent_complete)?;
+        }
+        if !story.assignees.is_empty() {
+            writeln!(out, "Assignees: {}", story.assignees.join(", "))?;
+        }
+        if story.estimate_hours.is_some() || story.actual_hours.is_some() {
+            writeln!(
+                out,
+                "Time: {} estimated, {} spent",
+                page_helpers::hours_label(story.estimate_hours, "unestimated"),
+                page_helpers::hours_label(story.actual_hours, "untracked")
+            )?;
+        }
+        if let Some(due_date) = story.due_date {
+            writeln!(out, "Due: {}", due_date)?;
+        }
+        if let Some(until) = story.snoozed_until {
+            if story.is_snoozed(Local::now().date_naive()) {
+                writeln!(out, "Snoozed until: {}", until)?;
+            }
+        }
+        if let Some(cycle_time) = self.db.cycle_time(self.story_id)? {
+            writeln!(
+                out,
+                "Cycle time: {}d {}h",
+                cycle_time.num_days(),
+                cycle_time.num_hours() % 24
+            )?;
+        }
+        if self.options.show_history && !story.status_history.is_empty() {
+            writeln!(
+                out,
+                "----------------------------- HISTORY -----------------------------"
+            )?;
+            for (status, at) in &story.status_history {
+                writeln!(
+                    out,
+                    "{}  {}",
+                    at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                    status.label(self.options.emoji)
+                )?;
+            }
+        }
+        writeln!(out)?;
+
+        let keys = &self.options.keys;
+        if self.options.read_only {
+            writeln!(out, "[{}] previous | [f] focus", keys.back)?;
+            return Ok(());
+        }
+        writeln!(
+            out,
+            "[{}] previous | [{}] update story | [{}] edit story | [{}] delete story | [r] reopen story | [*] pin story | [h] track time | [w] assignees | [%] percent complete | [a] add subtask | [x] extract subtasks from description | [t] toggle subtask | [z] snooze | [y] due date | [f] focus",
+            keys.back, keys.update, keys.edit, keys.delete
+        )?;
 
         Ok(())
     }
@@ -179,64 +598,544 @@ impl Page for StoryDetail {
     }
 
     fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        let keys = &self.options.keys;
+
         match input {
-            "p" => Ok(Some(Action::NavigateToPreviousPage)),
-            "u" => Ok(Some(Action::UpdateStoryStatus {
+            key if key == keys.back => Ok(Some(Action::NavigateToPreviousPage)),
+            key if key == keys.update => Ok(Some(Action::UpdateStoryStatus {
                 story_id: self.story_id,
             })),
-            "d" => Ok(Some(Action::DeleteStory {
+            key if key == keys.delete => Ok(Some(Action::DeleteStory {
                 story_id: self.story_id,
                 epic_id: self.epic_id,
             })),
-            "" => Ok(None),
+            key if key == keys.edit => Ok(Some(Action::EditStory {
+                story_id: self.story_id,
+            })),
+            "a" => Ok(Some(Action::CreateSubtask {
+                story_id: self.story_id,
+            })),
+            "t" => Ok(Some(Action::ToggleSubtask {
+                story_id: self.story_id,
+            })),
+            "h" => Ok(Some(Action::TrackStoryTime {
+                story_id: self.story_id,
+            })),
+            "w" => Ok(Some(Action::AssignStory {
+                story_id: self.story_id,
+            })),
+            "%" => Ok(Some(Action::SetStoryPercent {
+                story_id: self.story_id,
+            })),
+            "*" => Ok(Some(Action::ToggleStoryPin {
+                story_id: self.story_id,
+            })),
+            "r" => Ok(Some(Action::ReopenStory {
+                story_id: self.story_id,
+            })),
+            "x" => Ok(Some(Action::ExtractSubtasks {
+                story_id: self.story_id,
+            })),
+            "z" => Ok(Some(Action::SnoozeStory {
+                story_id: self.story_id,
+            })),
+            "y" => Ok(Some(Action::SetStoryDueDate {
+                story_id: self.story_id,
+            })),
+            "f" => Ok(Some(Action::NavigateToStoryFocus {
+                story_id: self.story_id,
+            })),
+            "" => {
+                let db_state = self.db.read_db()?;
+                let pages = match db_state.stories.get(&self.story_id) {
+                    Some(story) => self.description_pages(story).len(),
+                    None => 0,
+                };
+                match self.description_page + 1 < pages {
+                    true => Ok(Some(Action::PageStoryDescription {
+                        epic_id: self.epic_id,
+                        story_id: self.story_id,
+                        page: self.description_page + 1,
+                    })),
+                    false => Ok(None),
+                }
+            }
             _ => Ok(None),
         }
     }
 }
 
+pub struct StoryFocus {
+    pub story_id: u32,
+    pub db: Rc<JiraDatabase>,
+    pub options: Rc<DisplayOptions>,
+}
+
+fn focus_lines(story: &Story, emoji: bool, width: usize) -> Vec<String> {
+    let text_width = DESCRIPTION_WIDTH.min(width);
+    let margin = " ".repeat((width - text_width) / 2);
+    let centered = |text: &str| {
+        let padding = width.saturating_sub(text.chars().count()) / 2;
+        format!("{}{}", " ".repeat(padding), text)
+    };
+
+    let mut lines = vec![centered(&story.name), centered(&story.status.label(emoji))];
+    if !story.description.is_empty() {
+        lines.push(String::new());
+        for line in page_helpers::wrap_text(&story.description, text_width, 0).lines() {
+            lines.push(format!("{}{}", margin, line).trim_end().to_owned());
+        }
+    }
+    if !story.subtasks.is_empty() {
+        lines.push(String::new());
+        for subtask in &story.subtasks {
+            let mark = if subtask.done { "x" } else { " " };
+            lines.push(format!("{}[{}] {}", margin, mark, subtask.name));
+        }
+    }
+
+    lines
+}
+
+impl Page for StoryFocus {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+        let story = db_state
+            .stories
+            .get(&self.story_id)
+            .ok_or_else(|| anyhow!("could not find story!"))?;
+
+        let lines = focus_lines(story, self.options.emoji, terminal_width());
+        for _ in 0..terminal_height().saturating_sub(lines.len()) / 2 {
+            writeln!(out)?;
+        }
+        for line in lines {
+            writeln!(out, "{}", line)?;
+        }
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn handle_input(&self, _input: &str) -> Result<Option<Action>> {
+        Ok(Some(Action::NavigateToPreviousPage))
+    }
+}
+
+pub struct StoriesByStatus {
+    pub status: Status,
+    pub db: Rc<JiraDatabase>,
+    pub options: Rc<DisplayOptions>,
+}
+
+impl Page for StoriesByStatus {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+        let today = Local::now().date_naive();
+        let mut matches = self.db.stories_by_status(self.status.clone())?;
+        matches.retain(|(_, story_id)| {
+            db_state
+                .stories
+                .get(story_id)
+                .is_some_and(|story| self.options.show_snoozed || !story.is_snoozed(today))
+        });
+
+        writeln!(
+            out,
+            "------------------------ STORIES: {} ------------------------",
+            self.status.label(self.options.emoji)
+        )?;
+        for (epic_id, story_ids) in &matches.iter().chunk_by(|(epic_id, _)| *epic_id) {
+            let epic_name = db_state
+                .epics
+                .get(&epic_id)
+                .map(|epic| epic.name.as_str())
+                .unwrap_or_default();
+
+            writeln!(out)?;
+            writeln!(out, "{}: {}", ItemRef::Epic(epic_id), epic_name)?;
+            let mut table = Table::new(&[("id", 11), ("name", 32)]);
+            for (_, story_id) in story_ids {
+                if let Some(story) = db_state.stories.get(story_id) {
+                    let row = vec![ItemRef::Story(*story_id).to_string(), story.name.clone()];
+                    match self.options.ansi && story.is_snoozed(today) {
+                        true => table.add_dimmed_row(row),
+                        false => table.add_row(row),
+                    }
+                }
+            }
+            print_table(out, &table)?;
+        }
+        if matches.is_empty() {
+            writeln!(out, "No stories with this status.")?;
+        }
+        writeln!(out)?;
+        writeln!(out)?;
+
+        writeln!(
+            out,
+            "[{}] previous | [:id:] navigate to story",
+            self.options.keys.back
+        )?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            key if key == self.options.keys.back => Ok(Some(Action::NavigateToPreviousPage)),
+            "" => Ok(None),
+            input => match parse_item_ref(input) {
+                Some(ItemRef::Story(id)) | Some(ItemRef::Any(id)) => {
+                    let matches = self.db.stories_by_status(self.status.clone())?;
+                    match matches.into_iter().find(|(_, story_id)| *story_id == id) {
+                        Some((epic_id, story_id)) => {
+                            Ok(Some(Action::NavigateToStoryDetail { epic_id, story_id }))
+                        }
+                        None => Ok(None),
+                    }
+                }
+                _ => Ok(None),
+            },
+        }
+    }
+}
+
+pub struct ReadyQueue {
+    pub db: Rc<JiraDatabase>,
+    pub options: Rc<DisplayOptions>,
+}
+
+impl Page for ReadyQueue {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+
+        writeln!(
+            out,
+            "------------------------ READY TO START ------------------------"
+        )?;
+        let mut table = Table::new(&[("id", 11), ("name", 32), ("epic", 24)]);
+        for (epic_id, story_id) in self.db.ready_queue()? {
+            if let Some(story) = db_state.stories.get(&story_id) {
+                table.add_row(vec![
+                    ItemRef::Story(story_id).to_string(),
+                    story.name.clone(),
+                    db_state
+                        .epics
+                        .get(&epic_id)
+                        .map(|epic| epic.name.clone())
+                        .unwrap_or_default(),
+                ]);
+            }
+        }
+        print_table(out, &table)?;
+        writeln!(out)?;
+        writeln!(out)?;
+
+        writeln!(
+            out,
+            "[{}] previous | [:id:] navigate to story",
+            self.options.keys.back
+        )?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            key if key == self.options.keys.back => Ok(Some(Action::NavigateToPreviousPage)),
+            "" => Ok(None),
+            input => match parse_item_ref(input) {
+                Some(ItemRef::Story(id)) | Some(ItemRef::Any(id)) => Ok(self
+                    .db
+                    .ready_queue()?
+                    .into_iter()
+                    .find(|(_, story_id)| *story_id == id)
+                    .map(|(epic_id, story_id)| Action::NavigateToStoryDetail {
+                        epic_id,
+                        story_id,
+                    })),
+                _ => Ok(None),
+            },
+        }
+    }
+}
+
+pub struct StaleStories {
+    pub db: Rc<JiraDatabase>,
+    pub options: Rc<DisplayOptions>,
+}
+
+impl Page for StaleStories {
+    fn draw_page(&self, out: &mut dyn Write) -> Result<()> {
+        let db_state = self.db.read_db()?;
+        let now = Utc::now();
+
+        writeln!(
+            out,
+            "------------------- IDLE FOR {} DAYS OR MORE -------------------",
+            self.options.stale_days
+        )?;
+        let mut table = Table::new(&[("id", 11), ("name", 32), ("status", 16), ("idle", 8)]);
+        for story_id in self.db.stale_stories(self.options.stale_days)? {
+            if let Some(story) = db_state.stories.get(&story_id) {
+                let idle = story
+                    .status_history
+                    .last()
+                    .map(|(_, changed_at)| format!("{}d", (now - *changed_at).num_days()))
+                    .unwrap_or_default();
+                table.add_row(vec![
+                    ItemRef::Story(story_id).to_string(),
+                    story.name.clone(),
+                    story.status.label(self.options.emoji),
+                    idle,
+                ]);
+            }
+        }
+        print_table(out, &table)?;
+        writeln!(out)?;
+        writeln!(out)?;
+
+        writeln!(
+            out,
+            "[{}] previous | [:id:] navigate to story",
+            self.options.keys.back
+        )?;
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            key if key == self.options.keys.back => Ok(Some(Action::NavigateToPreviousPage)),
+            "" => Ok(None),
+            input => match parse_item_ref(input) {
+                Some(ItemRef::Story(id)) | Some(ItemRef::Any(id)) => {
+                    if !self
+                        .db
+                        .stale_stories(self.options.stale_days)?
+                        .contains(&id)
+                    {
+                        return Ok(None);
+                    }
+
+                    let db_state = self.db.read_db()?;
+                    Ok(db_state
+                        .epic_of(id)
+                        .map(|epic_id| Action::NavigateToStoryDetail {
+                            epic_id,
+                            story_id: id,
+                        }))
+                }
+                _ => Ok(None),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::db::test_utils::MockDB;
-    use crate::models::{Epic, Story};
+    use crate::models::{Epic, SortDirection, SortKey, Story};
+
+    #[test]
+    fn supports_utf8_should_check_locale_encoding() {
+        assert!(supports_utf8("en_US.UTF-8"));
+        assert!(supports_utf8("C.utf8"));
+        assert!(!supports_utf8("C"));
+        assert!(!supports_utf8(""));
+    }
 
     mod home_page {
         use super::*;
 
+        #[test]
+        fn draw_page_should_write_to_the_given_output() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            db.create_epic(Epic::new("Launch".to_owned(), "".to_owned()))
+                .unwrap();
+            let page = HomePage {
+                db,
+                sort: SortOrder::default(),
+                grouped: false,
+                options: Rc::new(DisplayOptions::default()),
+            };
+
+            let mut out = Vec::new();
+            page.draw_page(&mut out).unwrap();
+            let text = String::from_utf8(out).unwrap();
+
+            assert!(text.starts_with("----------------------------- EPICS"));
+            assert!(text.contains("EPIC-1"));
+            assert!(text.contains("☆ Launch"));
+            assert!(text.contains("Board completion: 0%"));
+            assert!(text.contains("Sorted by: id (ascending)"));
+        }
+
+        #[test]
+        fn group_epics_by_status_should_bucket_epics() {
+            let mut epics = HashMap::new();
+            for (id, status) in [
+                (4, Status::Closed),
+                (1, Status::Open),
+                (3, Status::Open),
+                (2, Status::Closed),
+                (5, Status::InProgress),
+            ] {
+                let mut epic = Epic::new("".to_owned(), "".to_owned());
+                epic.status = status;
+                epics.insert(id, epic);
+            }
+
+            let groups = group_epics_by_status(&epics)
+                .into_iter()
+                .map(|(status, group)| (status, group.into_iter().map(|(id, _)| *id).collect_vec()))
+                .collect_vec();
+
+            assert_eq!(
+                groups,
+                vec![
+                    (Status::Open, vec![1, 3]),
+                    (Status::InProgress, vec![5]),
+                    (Status::Closed, vec![2, 4]),
+                ]
+            );
+        }
+
+        #[test]
+        fn ordered_epics_should_put_starred_epics_first() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let first = db
+                .create_epic(Epic::new("a".to_owned(), "".to_owned()))
+                .unwrap();
+            let second = db
+                .create_epic(Epic::new("b".to_owned(), "".to_owned()))
+                .unwrap();
+            let third = db
+                .create_epic(Epic::new("c".to_owned(), "".to_owned()))
+                .unwrap();
+            db.toggle_epic_star(third).unwrap();
+
+            let page = HomePage {
+                db: Rc::clone(&db),
+                sort: SortOrder::default(),
+                grouped: false,
+                options: Rc::new(DisplayOptions::default()),
+            };
+            let epics = db.read_db().unwrap().epics;
+            let order = page
+                .ordered_epics(&epics)
+                .into_iter()
+                .map(|(id, _)| *id)
+                .collect_vec();
+
+            assert_eq!(order, vec![third, first, second]);
+        }
+
         #[test]
         fn draw_page_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
-            let page = HomePage { db };
-            assert!(page.draw_page().is_ok());
+            let page = HomePage {
+                db,
+                sort: SortOrder::default(),
+                grouped: false,
+                options: Rc::new(DisplayOptions::default()),
+            };
+            assert!(page.draw_page(&mut Vec::new()).is_ok());
         }
 
         #[test]
         fn handle_input_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
-            let page = HomePage { db };
+            let page = HomePage {
+                db,
+                sort: SortOrder::default(),
+                grouped: false,
+                options: Rc::new(DisplayOptions::default()),
+            };
             assert!(page.handle_input("").is_ok());
         }
 
+        #[test]
+        fn empty_board_should_still_accept_create() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
+            let page = HomePage {
+                db,
+                sort: SortOrder::default(),
+                grouped: false,
+                options: Rc::new(DisplayOptions::default()),
+            };
+            let mut out = Vec::new();
+            page.draw_page(&mut out).unwrap();
+            let out = String::from_utf8(out).unwrap();
+
+            assert!(
+                out.contains("No epics yet — press 'c' to create one."),
+                "{}",
+                out
+            );
+            assert_eq!(page.handle_input("c").unwrap(), Some(Action::CreateEpic));
+        }
+
+        #[test]
+        fn empty_board_should_not_offer_create_when_read_only() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+
+            let page = HomePage {
+                db,
+                sort: SortOrder::default(),
+                grouped: false,
+                options: Rc::new(DisplayOptions {
+                    read_only: true,
+                    ..DisplayOptions::default()
+                }),
+            };
+            let mut out = Vec::new();
+            page.draw_page(&mut out).unwrap();
+            let out = String::from_utf8(out).unwrap();
+
+            assert!(out.contains("No epics yet.\n"), "{}", out);
+            assert!(!out.contains("to create one"), "{}", out);
+        }
+
         #[test]
         fn handle_input_should_return_the_correct_actions() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
             let epic = Epic::new("".to_owned(), "".to_owned());
 
             let epic_id = db.create_epic(epic).unwrap();
 
-            let page = HomePage { db };
+            let page = HomePage {
+                db,
+                sort: SortOrder::default(),
+                grouped: false,
+                options: Rc::new(DisplayOptions::default()),
+            };
 
             let q = "q";
             let c = "c";
+            let s = "s";
+            let o = "o";
             let valid_epic_id = epic_id.to_string();
             let invalid_epic_id = "999";
             let junk_input = "j983f2j";
@@ -245,10 +1144,44 @@ mod tests {
 
             assert_eq!(page.handle_input(q).unwrap(), Some(Action::Exit));
             assert_eq!(page.handle_input(c).unwrap(), Some(Action::CreateEpic));
+            assert_eq!(
+                page.handle_input(s).unwrap(),
+                Some(Action::NavigateToStoriesByStatus)
+            );
+            assert_eq!(
+                page.handle_input("i").unwrap(),
+                Some(Action::NavigateToStaleStories)
+            );
+            assert_eq!(
+                page.handle_input("w").unwrap(),
+                Some(Action::NavigateToReadyQueue)
+            );
+            assert_eq!(page.handle_input("g").unwrap(), Some(Action::GoTo));
+            assert_eq!(
+                page.handle_input("l").unwrap(),
+                Some(Action::GroupHomePage { grouped: true })
+            );
+            assert_eq!(
+                page.handle_input("n").unwrap(),
+                Some(Action::CreateStoryInPickedEpic)
+            );
+            assert_eq!(page.handle_input("a").unwrap(), Some(Action::QuickAddStory));
+            assert_eq!(page.handle_input("r").unwrap(), Some(Action::ReplaceText));
+            assert_eq!(
+                page.handle_input(o).unwrap(),
+                Some(Action::SortHomePage {
+                    sort: SortOrder::default().next()
+                })
+            );
             assert_eq!(
                 page.handle_input(&valid_epic_id).unwrap(),
                 Some(Action::NavigateToEpicDetail { epic_id: 1 })
             );
+            assert_eq!(
+                page.handle_input("EPIC-1").unwrap(),
+                Some(Action::NavigateToEpicDetail { epic_id: 1 })
+            );
+            assert_eq!(page.handle_input("STORY-1").unwrap(), None);
             assert_eq!(page.handle_input(invalid_epic_id).unwrap(), None);
             assert_eq!(page.handle_input(junk_input).unwrap(), None);
             assert_eq!(
@@ -260,52 +1193,148 @@ mod tests {
                 None
             );
         }
-    }
+    }
+
+    mod epic_detail_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = EpicDetail {
+                epic_id,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+                sort: SortOrder::default(),
+            };
+            assert!(page.draw_page(&mut Vec::new()).is_ok());
+        }
+
+        #[test]
+        fn handle_input_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = EpicDetail {
+                epic_id,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+                sort: SortOrder::default(),
+            };
+            assert!(page.handle_input("").is_ok());
+        }
 
-    mod epic_detail_page {
-        use super::*;
+        #[test]
+        fn draw_page_should_list_pinned_stories_first() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let names = ["alpha", "beta", "gamma", "delta"];
+            let story_ids = names
+                .iter()
+                .map(|name| {
+                    db.create_story(Story::new(name.to_string(), "".to_owned()), epic_id)
+                        .unwrap()
+                })
+                .collect_vec();
+            db.toggle_story_pin(story_ids[3]).unwrap();
+            db.toggle_story_pin(story_ids[1]).unwrap();
+
+            let page = EpicDetail {
+                epic_id,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+                sort: SortOrder::default(),
+            };
+            let mut out = Vec::new();
+            page.draw_page(&mut out).unwrap();
+            let out = String::from_utf8(out).unwrap();
+            let list = &out[out.find("STORIES").unwrap()..];
+
+            let positions =
+                ["* beta", "* delta", "alpha", "gamma"].map(|name| list.find(name).unwrap());
+            assert!(positions.is_sorted(), "{}", out);
+        }
 
         #[test]
-        fn draw_page_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+        fn draw_page_should_only_list_stories_of_the_epic() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
                 .unwrap();
+            let other_epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            db.create_story(Story::new("mine".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.create_story(
+                Story::new("theirs".to_owned(), "".to_owned()),
+                other_epic_id,
+            )
+            .unwrap();
+
+            let page = EpicDetail {
+                epic_id,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+                sort: SortOrder::default(),
+            };
+            let mut out = Vec::new();
+            page.draw_page(&mut out).unwrap();
+            let out = String::from_utf8(out).unwrap();
 
-            let page = EpicDetail { epic_id, db };
-            assert!(page.draw_page().is_ok());
+            assert!(out.contains("mine"), "{}", out);
+            assert!(!out.contains("theirs"), "{}", out);
         }
 
         #[test]
-        fn handle_input_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+        fn draw_page_should_list_the_definition_of_done() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
                 .unwrap();
+            db.add_dod_item(epic_id, "Docs updated".to_owned()).unwrap();
+            db.add_dod_item(epic_id, "Released".to_owned()).unwrap();
+            db.toggle_dod_item(epic_id, 0).unwrap();
 
-            let page = EpicDetail { epic_id, db };
-            assert!(page.handle_input("").is_ok());
+            let page = EpicDetail {
+                epic_id,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+                sort: SortOrder::default(),
+            };
+            let mut out = Vec::new();
+            page.draw_page(&mut out).unwrap();
+            let out = String::from_utf8(out).unwrap();
+
+            assert!(out.contains("  1. [x] Docs updated"), "{}", out);
+            assert!(out.contains("  2. [ ] Released"), "{}", out);
+            assert!(out.contains("1 of 2 definition of done items checked"));
         }
 
         #[test]
         fn draw_page_should_throw_error_for_invalid_epic_id() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
-            let page = EpicDetail { epic_id: 999, db };
-            assert!(page.draw_page().is_err());
+            let page = EpicDetail {
+                epic_id: 999,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+                sort: SortOrder::default(),
+            };
+            assert!(page.draw_page(&mut Vec::new()).is_err());
         }
 
         #[test]
         fn handle_input_should_return_the_correct_actions() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
@@ -314,19 +1343,25 @@ mod tests {
                 .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
                 .unwrap();
 
-            let page = EpicDetail { epic_id, db };
+            let page = EpicDetail {
+                epic_id,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+                sort: SortOrder::default(),
+            };
 
-            let p = "p";
+            let b = "b";
             let u = "u";
             let d = "d";
+            let k = "k";
             let c = "c";
             let invalid_story_id = "999";
             let junk_input = "j983f2j";
-            let junk_input_with_valid_prefix = "p983f2j";
-            let input_with_trailing_white_spaces = "p\n";
+            let junk_input_with_valid_prefix = "b983f2j";
+            let input_with_trailing_white_spaces = "b\n";
 
             assert_eq!(
-                page.handle_input(p).unwrap(),
+                page.handle_input(b).unwrap(),
                 Some(Action::NavigateToPreviousPage)
             );
             assert_eq!(
@@ -337,6 +1372,30 @@ mod tests {
                 page.handle_input(d).unwrap(),
                 Some(Action::DeleteEpic { epic_id: 1 })
             );
+            assert_eq!(
+                page.handle_input(k).unwrap(),
+                Some(Action::DeleteEpicKeepStories { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("m").unwrap(),
+                Some(Action::MergeEpic { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("*").unwrap(),
+                Some(Action::ToggleEpicStar { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("a").unwrap(),
+                Some(Action::AddDodItem { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("t").unwrap(),
+                Some(Action::ToggleDodItem { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input("e").unwrap(),
+                Some(Action::EditEpic { epic_id: 1 })
+            );
             assert_eq!(
                 page.handle_input(c).unwrap(),
                 Some(Action::CreateStory { epic_id: 1 })
@@ -348,6 +1407,14 @@ mod tests {
                     story_id: 2
                 })
             );
+            assert_eq!(
+                page.handle_input("story-2").unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id: 1,
+                    story_id: 2
+                })
+            );
+            assert_eq!(page.handle_input("EPIC-2").unwrap(), None);
             assert_eq!(page.handle_input(invalid_story_id).unwrap(), None);
             assert_eq!(page.handle_input(junk_input).unwrap(), None);
             assert_eq!(
@@ -359,6 +1426,128 @@ mod tests {
                 None
             );
         }
+
+        #[test]
+        fn visible_stories_should_hide_stories_snoozed_into_the_future() {
+            let today = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+            let mut snoozed = Story::new("snoozed".to_owned(), "".to_owned());
+            snoozed.snoozed_until = today.succ_opt();
+            let mut woken = Story::new("woken".to_owned(), "".to_owned());
+            woken.snoozed_until = today.pred_opt();
+            let active = Story::new("active".to_owned(), "".to_owned());
+            let stories = HashMap::from([(1, snoozed), (2, woken), (3, active)]);
+
+            let ids = |show_snoozed| {
+                visible_stories(
+                    &[1, 2, 3],
+                    &stories,
+                    today,
+                    show_snoozed,
+                    SortOrder::default(),
+                )
+                .into_iter()
+                .map(|(id, _)| *id)
+                .collect_vec()
+            };
+
+            assert_eq!(ids(false), vec![2, 3]);
+            assert_eq!(ids(true), vec![1, 2, 3]);
+        }
+
+        #[test]
+        fn sorting_by_status_should_group_stories_and_keep_stored_order() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_ids: Vec<u32> = (0..4)
+                .map(|_| {
+                    db.create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                        .unwrap()
+                })
+                .collect();
+            db.update_story_status(story_ids[0], Status::InProgress)
+                .unwrap();
+            db.update_story_status(story_ids[2], Status::InProgress)
+                .unwrap();
+            let sort = SortOrder {
+                key: SortKey::Status,
+                direction: SortDirection::Ascending,
+            };
+
+            let db_state = db.read_db().unwrap();
+            let statuses = visible_stories(
+                &db_state.epics[&epic_id].stories,
+                &db_state.stories,
+                Local::now().date_naive(),
+                false,
+                sort,
+            )
+            .into_iter()
+            .map(|(id, story)| (*id, story.status.clone()))
+            .collect_vec();
+            assert_eq!(
+                statuses,
+                vec![
+                    (story_ids[1], Status::Open),
+                    (story_ids[3], Status::Open),
+                    (story_ids[0], Status::InProgress),
+                    (story_ids[2], Status::InProgress),
+                ]
+            );
+
+            let page = EpicDetail {
+                epic_id,
+                db: db.clone(),
+                options: Rc::new(DisplayOptions::default()),
+                sort,
+            };
+            assert!(page.draw_page(&mut Vec::new()).is_ok());
+            assert_eq!(
+                page.handle_input("o").unwrap(),
+                Some(Action::SortEpicDetail {
+                    epic_id,
+                    sort: sort.next()
+                })
+            );
+            assert_eq!(
+                db.read_db().unwrap().epics.get(&epic_id).unwrap().stories,
+                story_ids
+            );
+        }
+
+        #[test]
+        fn handle_input_should_follow_remapped_keys() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = EpicDetail {
+                epic_id,
+                db,
+                options: Rc::new(DisplayOptions {
+                    keys: Keymap {
+                        back: "p".to_owned(),
+                        delete: "v".to_owned(),
+                        ..Keymap::default()
+                    },
+                    ..DisplayOptions::default()
+                }),
+                sort: SortOrder::default(),
+            };
+
+            assert_eq!(
+                page.handle_input("p").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("v").unwrap(),
+                Some(Action::DeleteEpic { epic_id })
+            );
+            assert_eq!(page.handle_input("b").unwrap(), None);
+            assert_eq!(page.handle_input("d").unwrap(), None);
+        }
     }
 
     mod story_detail_page {
@@ -366,9 +1555,7 @@ mod tests {
 
         #[test]
         fn draw_page_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
@@ -381,15 +1568,15 @@ mod tests {
                 epic_id,
                 story_id,
                 db,
+                options: Rc::new(DisplayOptions::default()),
+                description_page: 0,
             };
-            assert!(page.draw_page().is_ok());
+            assert!(page.draw_page(&mut Vec::new()).is_ok());
         }
 
         #[test]
         fn handle_input_should_not_throw_error() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
@@ -402,15 +1589,63 @@ mod tests {
                 epic_id,
                 story_id,
                 db,
+                options: Rc::new(DisplayOptions::default()),
+                description_page: 0,
             };
             assert!(page.handle_input("").is_ok());
         }
 
+        #[test]
+        fn draw_page_should_show_one_description_page_at_a_time() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(
+                    Story::new("".to_owned(), "first\nsecond\nthird".to_owned()),
+                    epic_id,
+                )
+                .unwrap();
+            let page = |description_page| StoryDetail {
+                epic_id,
+                story_id,
+                db: db.clone(),
+                options: Rc::new(DisplayOptions {
+                    page_size: Some(2),
+                    ..DisplayOptions::default()
+                }),
+                description_page,
+            };
+            let draw = |page: &StoryDetail| {
+                let mut out = Vec::new();
+                page.draw_page(&mut out).unwrap();
+                String::from_utf8(out).unwrap()
+            };
+
+            let out = draw(&page(0));
+            assert!(out.contains("\n  second\n"), "{}", out);
+            assert!(!out.contains("\n  third\n"), "{}", out);
+            assert!(out.contains("-- page 1 of 2, press enter for more --"));
+            assert_eq!(
+                page(0).handle_input("").unwrap(),
+                Some(Action::PageStoryDescription {
+                    epic_id,
+                    story_id,
+                    page: 1
+                })
+            );
+
+            let out = draw(&page(1));
+            assert!(out.contains("\n  third\n"), "{}", out);
+            assert!(!out.contains("\n  second\n"), "{}", out);
+            assert!(out.contains("-- page 2 of 2 --"));
+            assert_eq!(page(1).handle_input("").unwrap(), None);
+        }
+
         #[test]
         fn draw_page_should_throw_error_for_invalid_story_id() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
@@ -423,15 +1658,15 @@ mod tests {
                 epic_id,
                 story_id: 999,
                 db,
+                options: Rc::new(DisplayOptions::default()),
+                description_page: 0,
             };
-            assert!(page.draw_page().is_err());
+            assert!(page.draw_page(&mut Vec::new()).is_err());
         }
 
         #[test]
         fn handle_input_should_return_the_correct_actions() {
-            let db = Rc::new(JiraDatabase {
-                database: Box::new(MockDB::new()),
-            });
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
 
             let epic_id = db
                 .create_epic(Epic::new("".to_owned(), "".to_owned()))
@@ -444,28 +1679,72 @@ mod tests {
                 epic_id,
                 story_id,
                 db,
+                options: Rc::new(DisplayOptions::default()),
+                description_page: 0,
             };
 
-            let p = "p";
+            let b = "b";
             let u = "u";
             let d = "d";
+            let a = "a";
+            let t = "t";
             let some_number = "1";
             let junk_input = "j983f2j";
-            let junk_input_with_valid_prefix = "p983f2j";
-            let input_with_trailing_white_spaces = "p\n";
+            let junk_input_with_valid_prefix = "b983f2j";
+            let input_with_trailing_white_spaces = "b\n";
 
             assert_eq!(
-                page.handle_input(p).unwrap(),
+                page.handle_input(b).unwrap(),
                 Some(Action::NavigateToPreviousPage)
             );
             assert_eq!(
                 page.handle_input(u).unwrap(),
                 Some(Action::UpdateStoryStatus { story_id })
             );
+            assert_eq!(
+                page.handle_input("e").unwrap(),
+                Some(Action::EditStory { story_id })
+            );
             assert_eq!(
                 page.handle_input(d).unwrap(),
                 Some(Action::DeleteStory { epic_id, story_id })
             );
+            assert_eq!(
+                page.handle_input(a).unwrap(),
+                Some(Action::CreateSubtask { story_id })
+            );
+            assert_eq!(
+                page.handle_input("x").unwrap(),
+                Some(Action::ExtractSubtasks { story_id })
+            );
+            assert_eq!(
+                page.handle_input("r").unwrap(),
+                Some(Action::ReopenStory { story_id })
+            );
+            assert_eq!(
+                page.handle_input("*").unwrap(),
+                Some(Action::ToggleStoryPin { story_id })
+            );
+            assert_eq!(
+                page.handle_input("h").unwrap(),
+                Some(Action::TrackStoryTime { story_id })
+            );
+            assert_eq!(
+                page.handle_input("w").unwrap(),
+                Some(Action::AssignStory { story_id })
+            );
+            assert_eq!(
+                page.handle_input("%").unwrap(),
+                Some(Action::SetStoryPercent { story_id })
+            );
+            assert_eq!(
+                page.handle_input("y").unwrap(),
+                Some(Action::SetStoryDueDate { story_id })
+            );
+            assert_eq!(
+                page.handle_input(t).unwrap(),
+                Some(Action::ToggleSubtask { story_id })
+            );
             assert_eq!(page.handle_input(some_number).unwrap(), None);
             assert_eq!(page.handle_input(junk_input).unwrap(), None);
             assert_eq!(
@@ -478,4 +1757,209 @@ mod tests {
             );
         }
     }
+
+    mod story_focus_page {
+        use super::*;
+
+        #[test]
+        fn focus_lines_should_center_story_content() {
+            let mut story = Story::new(
+                "Write docs".to_owned(),
+                "Explain the setup steps clearly".to_owned(),
+            );
+            story.add_subtask("Draft".to_owned());
+            story.add_subtask("Review".to_owned());
+            story.toggle_subtask(0);
+
+            assert_eq!(
+                focus_lines(&story, false, 20),
+                vec![
+                    "     Write docs",
+                    "        OPEN",
+                    "",
+                    "Explain the setup",
+                    "steps clearly",
+                    "",
+                    "[x] Draft",
+                    "[ ] Review",
+                ]
+            );
+        }
+
+        #[test]
+        fn handle_input_should_leave_focus_on_any_key() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+
+            let detail = StoryDetail {
+                epic_id,
+                story_id,
+                db: db.clone(),
+                options: Rc::new(DisplayOptions::default()),
+                description_page: 0,
+            };
+            assert_eq!(
+                detail.handle_input("f").unwrap(),
+                Some(Action::NavigateToStoryFocus { story_id })
+            );
+
+            let page = StoryFocus {
+                story_id,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+            };
+            assert!(page.draw_page(&mut Vec::new()).is_ok());
+            for input in ["", "q", "x"] {
+                assert_eq!(
+                    page.handle_input(input).unwrap(),
+                    Some(Action::NavigateToPreviousPage)
+                );
+            }
+        }
+    }
+
+    mod stories_by_status_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_not_throw_error() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let _ = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+
+            let page = StoriesByStatus {
+                status: Status::Open,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+            };
+            assert!(page.draw_page(&mut Vec::new()).is_ok());
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            let closed_story_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.update_story_status(closed_story_id, Status::Closed)
+                .unwrap();
+
+            let page = StoriesByStatus {
+                status: Status::Open,
+                db,
+                options: Rc::new(DisplayOptions::default()),
+            };
+
+            assert_eq!(
+                page.handle_input("b").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input(&story_id.to_string()).unwrap(),
+                Some(Action::NavigateToStoryDetail { epic_id, story_id })
+            );
+            assert_eq!(
+                page.handle_input(&closed_story_id.to_string()).unwrap(),
+                None
+            );
+            assert_eq!(page.handle_input("999").unwrap(), None);
+            assert_eq!(page.handle_input("j983f2j").unwrap(), None);
+        }
+    }
+
+    mod ready_queue_page {
+        use super::*;
+
+        #[test]
+        fn handle_input_should_navigate_to_ready_stories_only() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let ready_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            let started_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.update_story_status(started_id, Status::InProgress)
+                .unwrap();
+
+            let page = ReadyQueue {
+                db,
+                options: Rc::new(DisplayOptions::default()),
+            };
+
+            assert!(page.draw_page(&mut Vec::new()).is_ok());
+            assert_eq!(
+                page.handle_input("b").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input(&ready_id.to_string()).unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id,
+                    story_id: ready_id
+                })
+            );
+            assert_eq!(page.handle_input(&started_id.to_string()).unwrap(), None);
+        }
+    }
+
+    mod stale_stories_page {
+        use super::*;
+
+        #[test]
+        fn handle_input_should_navigate_to_stale_stories_only() {
+            let db = Rc::new(JiraDatabase::with_database(Box::new(MockDB::new())));
+            let epic_id = db
+                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .unwrap();
+            let long_ago = Utc::now() - chrono::Duration::days(30);
+            let mut old = Story::new("".to_owned(), "".to_owned());
+            old.status_history = vec![(Status::Open, long_ago)];
+            old.updated_at = Some(long_ago);
+            let old_id = db.create_story(old, epic_id).unwrap();
+            let fresh_id = db
+                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+
+            let page = StaleStories {
+                db,
+                options: Rc::new(DisplayOptions {
+                    stale_days: 14,
+                    ..DisplayOptions::default()
+                }),
+            };
+
+            assert!(page.draw_page(&mut Vec::new()).is_ok());
+            assert_eq!(
+                page.handle_input("b").unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input(&old_id.to_string()).unwrap(),
+                Some(Action::NavigateToStoryDetail {
+                    epic_id,
+                    story_id: old_id
+                })
+            );
+            assert_eq!(page.handle_input(&fresh_id.to_string()).unwrap(), None);
+        }
+    }
 }
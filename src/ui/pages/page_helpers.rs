@@ -1,4 +1,7 @@
 use ellipse::Ellipse;
+use itertools::Itertools;
+
+use crate::models::Status;
 
 pub fn get_column_string(text: &str, width: usize) -> String {
     if text.len() > width {
@@ -23,6 +26,172 @@ pub fn get_column_string(text: &str, width: usize) -> String {
     }
 }
 
+pub fn paginate_text(text: &str, height: usize) -> Vec<String> {
+    text.lines()
+        .collect::<Vec<_>>()
+        .chunks(height.max(1))
+        .map(|chunk| chunk.join("\n"))
+        .collect()
+}
+
+pub fn wrap_text(text: &str, width: usize, indent: usize) -> String {
+    let available = width.saturating_sub(indent).max(1);
+    let padding = " ".repeat(indent);
+    let mut lines = vec![];
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word.chars().collect::<Vec<_>>();
+            while word.len() > available {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                lines.push(word.drain(..available).collect());
+            }
+            if word.is_empty() {
+                continue;
+            }
+
+            let line_len = line.chars().count();
+            if line_len > 0 && line_len + 1 + word.len() > available {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.extend(word);
+        }
+        lines.push(line);
+    }
+
+    lines
+        .iter()
+        .map(|line| match line.is_empty() {
+            true => String::new(),
+            false => format!("{}{}", padding, line),
+        })
+        .join("\n")
+}
+
+pub fn progress_bar(done: usize, total: usize, width: usize) -> String {
+    let filled = match total {
+        0 => 0,
+        total => (done.min(total) * width + total / 2) / total,
+    };
+
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+pub fn status_breakdown<'a>(statuses: impl IntoIterator<Item = &'a Status>) -> String {
+    let mut counts = [0; 4];
+    for status in statuses {
+        counts[match status {
+            Status::Open => 0,
+            Status::InProgress => 1,
+            Status::Resolved => 2,
+            Status::Closed => 3,
+        }] += 1;
+    }
+
+    match counts.iter().sum::<usize>() {
+        0 => "no stories".to_owned(),
+        _ => ["Open", "In-Progress", "Resolved", "Closed"]
+            .iter()
+            .zip(counts)
+            .map(|(label, count)| format!("{} {}", label, count))
+            .join(" · "),
+    }
+}
+
+pub fn hours_label(hours: Option<f32>, missing: &str) -> String {
+    match hours {
+        Some(hours) => format!("{:.1}h", hours),
+        None => missing.to_owned(),
+    }
+}
+
+pub struct Table {
+    columns: Vec<(String, usize)>,
+    rows: Vec<(Vec<String>, bool)>,
+}
+
+impl Table {
+    pub fn new(columns: &[(&str, usize)]) -> Self {
+        Self {
+            columns: columns
+                .iter()
+                .map(|(title, max_width)| (title.to_string(), *max_width))
+                .collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push((row, false));
+    }
+
+    pub fn add_dimmed_row(&mut self, row: Vec<String>) {
+        self.rows.push((row, true));
+    }
+
+    pub fn render(&self) -> Vec<String> {
+        let widths = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, (title, max_width))| {
+                self.rows
+                    .iter()
+                    .filter_map(|(row, _)| row.get(index))
+                    .map(|cell| cell.chars().count())
+                    .chain([title.chars().count()])
+                    .max()
+                    .unwrap_or_default()
+                    .min(*max_width)
+            })
+            .collect_vec();
+
+        let header = self
+            .columns
+            .iter()
+            .map(|(title, _)| title.clone())
+            .collect();
+        [(header, false)]
+            .iter()
+            .chain(&self.rows)
+            .map(|(row, dimmed): &(Vec<String>, bool)| {
+                let line = widths
+                    .iter()
+                    .enumerate()
+                    .map(|(index, width)| {
+                        fit_cell(row.get(index).map(String::as_str).unwrap_or(""), *width)
+                    })
+                    .join(" | ")
+                    .trim_end()
+                    .to_owned();
+                match dimmed {
+                    true => format!("\x1b[2m{}\x1b[0m", line),
+                    false => line,
+                }
+            })
+            .collect()
+    }
+}
+
+fn fit_cell(text: &str, width: usize) -> String {
+    let length = text.chars().count();
+    if length > width {
+        if width <= 3 {
+            return ".".repeat(width);
+        }
+
+        return text.chars().take(width - 3).chain("...".chars()).collect();
+    }
+
+    format!("{}{}", text, " ".repeat(width - length))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,4 +230,103 @@ mod tests {
         assert_eq!(get_column_string(text3, width), "testme".to_owned());
         assert_eq!(get_column_string(text4, width), "tes...".to_owned());
     }
+
+    #[test]
+    fn test_paginate_text() {
+        let text = "one\ntwo\nthree\nfour\nfive";
+
+        assert_eq!(
+            paginate_text(text, 2),
+            vec!["one\ntwo", "three\nfour", "five"]
+        );
+        assert_eq!(paginate_text(text, 5), vec![text]);
+        assert_eq!(paginate_text(text, 0).len(), 5);
+        assert!(paginate_text("", 3).is_empty());
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        let text = "the quick brown fox jumps over the lazy dog";
+
+        assert_eq!(
+            wrap_text(text, 16, 0),
+            "the quick brown\nfox jumps over\nthe lazy dog"
+        );
+        assert_eq!(
+            wrap_text(text, 22, 2),
+            "  the quick brown fox\n  jumps over the lazy\n  dog"
+        );
+        assert_eq!(wrap_text("abcdefghij", 4, 0), "abcd\nefgh\nij");
+        assert_eq!(wrap_text("first\n\nsecond", 10, 1), " first\n\n second");
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(0, 4, 8), "[--------]");
+        assert_eq!(progress_bar(2, 4, 8), "[####----]");
+        assert_eq!(progress_bar(4, 4, 8), "[########]");
+        assert_eq!(progress_bar(0, 0, 8), "[--------]");
+        assert_eq!(progress_bar(1, 3, 0), "[]");
+    }
+
+    #[test]
+    fn test_status_breakdown() {
+        let statuses = [
+            Status::Closed,
+            Status::Open,
+            Status::InProgress,
+            Status::Closed,
+            Status::Open,
+            Status::Closed,
+        ];
+
+        assert_eq!(
+            status_breakdown(&statuses),
+            "Open 2 · In-Progress 1 · Resolved 0 · Closed 3"
+        );
+        assert_eq!(status_breakdown(&[]), "no stories");
+    }
+
+    #[test]
+    fn test_hours_label() {
+        assert_eq!(hours_label(Some(2.5), "unestimated"), "2.5h");
+        assert_eq!(hours_label(Some(3.0), "unestimated"), "3.0h");
+        assert_eq!(hours_label(None, "unestimated"), "unestimated");
+    }
+
+    #[test]
+    fn test_table_render() {
+        let mut table = Table::new(&[("id", 10), ("name", 8), ("status", 10)]);
+        table.add_row(vec!["EPIC-1".to_owned(), "a".to_owned(), "OPEN".to_owned()]);
+        table.add_row(vec![
+            "EPIC-12".to_owned(),
+            "a much longer name".to_owned(),
+            "Closed".to_owned(),
+        ]);
+
+        let lines = table.render();
+
+        assert_eq!(
+            lines,
+            vec![
+                "id      | name     | status",
+                "EPIC-1  | a        | OPEN",
+                "EPIC-12 | a muc... | Closed",
+            ]
+        );
+        let separators = |line: &str| line.match_indices('|').map(|(at, _)| at).collect_vec();
+        assert_eq!(separators(&lines[1]), separators(&lines[2]));
+    }
+
+    #[test]
+    fn test_table_render_dimmed_row() {
+        let mut table = Table::new(&[("id", 10), ("name", 8)]);
+        table.add_row(vec!["STORY-1".to_owned(), "a".to_owned()]);
+        table.add_dimmed_row(vec!["STORY-2".to_owned(), "b".to_owned()]);
+
+        assert_eq!(
+            table.render(),
+            vec!["id      | name", "STORY-1 | a", "\x1b[2mSTORY-2 | b\x1b[0m"]
+        );
+    }
 }
@@ -1,78 +1,442 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use itertools::Itertools;
+
 use crate::{
+    error::JiraError,
     io_utils::get_user_input,
-    models::{Epic, Status, Story},
+    models::{parse_date_input, Epic, ItemEdit, Status, Story, TextFields},
 };
 
+pub type DatePrompt = Box<dyn Fn() -> Result<Option<NaiveDate>>>;
+pub type DueDatePrompt = Box<dyn Fn() -> Result<Option<Option<NaiveDate>>>>;
+pub type EditPrompt = Box<dyn Fn(&str, &str, &Status) -> Result<ItemEdit>>;
+pub type EpicPicker = Box<dyn Fn(&[(u32, String)]) -> Result<Option<u32>>>;
+pub type ReplacePrompt = Box<dyn Fn() -> Result<Option<(String, String, TextFields, bool)>>>;
+pub type StatusPicker = Box<dyn Fn(&[(Status, usize)]) -> Result<Option<Status>>>;
+pub type TimePrompt = Box<dyn Fn() -> Result<(Option<f32>, Option<f32>)>>;
+pub type UncheckedPrompt = Box<dyn Fn(&[&str]) -> Result<bool>>;
+
 pub struct Prompts {
-    pub create_epic: Box<dyn Fn() -> Epic>,
-    pub create_story: Box<dyn Fn() -> Story>,
-    pub delete_epic: Box<dyn Fn() -> bool>,
-    pub delete_story: Box<dyn Fn() -> bool>,
-    pub update_status: Box<dyn Fn() -> Option<Status>>,
+    pub create_epic: Box<dyn Fn() -> Result<Epic>>,
+    pub create_story: Box<dyn Fn() -> Result<Story>>,
+    pub delete_epic: Box<dyn Fn() -> Result<bool>>,
+    pub delete_epic_keep_stories: Box<dyn Fn() -> Result<bool>>,
+    pub reassign_stories: Box<dyn Fn() -> Result<Option<u32>>>,
+    pub merge_into: Box<dyn Fn() -> Result<Option<u32>>>,
+    pub pick_epic: EpicPicker,
+    pub delete_story: Box<dyn Fn() -> Result<bool>>,
+    pub update_status: Box<dyn Fn() -> Result<Option<Status>>>,
+    pub reopen_epic: Box<dyn Fn(usize) -> Result<bool>>,
+    pub reopen_reason: Box<dyn Fn() -> Result<String>>,
+    pub close_unchecked_dod: UncheckedPrompt,
+    pub pick_status: StatusPicker,
+    pub create_subtask: Box<dyn Fn() -> Result<String>>,
+    pub toggle_subtask: Box<dyn Fn() -> Result<Option<usize>>>,
+    pub create_dod_item: Box<dyn Fn() -> Result<String>>,
+    pub toggle_dod_item: Box<dyn Fn() -> Result<Option<usize>>>,
+    pub snooze_until: DatePrompt,
+    pub due_date: DueDatePrompt,
+    pub goto: Box<dyn Fn() -> Result<String>>,
+    pub quick_add: Box<dyn Fn() -> Result<String>>,
+    pub replace_text: ReplacePrompt,
+    pub track_time: TimePrompt,
+    pub assign: Box<dyn Fn() -> Result<String>>,
+    pub percent_complete: Box<dyn Fn() -> Result<Option<u8>>>,
+    pub edit_item: EditPrompt,
 }
 
 impl Prompts {
     pub fn new() -> Self {
+        Self::with_confirm_default(false)
+    }
+
+    pub fn with_confirm_default(default_yes: bool) -> Self {
         Self {
             create_epic: Box::new(create_epic_prompt),
             create_story: Box::new(create_story_prompt),
-            delete_epic: Box::new(delete_epic_prompt),
-            delete_story: Box::new(delete_story_prompt),
+            delete_epic: Box::new(move || delete_epic_prompt(default_yes)),
+            delete_epic_keep_stories: Box::new(move || {
+                delete_epic_keep_stories_prompt(default_yes)
+            }),
+            reassign_stories: Box::new(reassign_stories_prompt),
+            merge_into: Box::new(merge_into_prompt),
+            pick_epic: Box::new(pick_epic_prompt),
+            delete_story: Box::new(move || delete_story_prompt(default_yes)),
             update_status: Box::new(update_status_prompt),
+            reopen_epic: Box::new(move |closed| reopen_epic_prompt(closed, default_yes)),
+            reopen_reason: Box::new(reopen_reason_prompt),
+            close_unchecked_dod: Box::new(move |unchecked| {
+                close_unchecked_dod_prompt(unchecked, default_yes)
+            }),
+            pick_status: Box::new(pick_status_prompt),
+            create_subtask: Box::new(create_subtask_prompt),
+            toggle_subtask: Box::new(toggle_subtask_prompt),
+            create_dod_item: Box::new(create_dod_item_prompt),
+            toggle_dod_item: Box::new(toggle_dod_item_prompt),
+            snooze_until: Box::new(snooze_until_prompt),
+            due_date: Box::new(due_date_prompt),
+            goto: Box::new(goto_prompt),
+            quick_add: Box::new(quick_add_prompt),
+            replace_text: Box::new(replace_text_prompt),
+            track_time: Box::new(track_time_prompt),
+            assign: Box::new(assign_prompt),
+            percent_complete: Box::new(percent_complete_prompt),
+            edit_item: Box::new(edit_item_prompt),
         }
     }
 }
 
-fn create_epic_prompt() -> Epic {
+fn create_epic_prompt() -> Result<Epic> {
     println!("----------------------------");
     println!("Epic Name:");
-    let name = get_user_input().trim().to_owned();
+    let name = read_input()?.trim().to_owned();
     println!("Epic Description:");
-    let description = get_user_input().trim().to_owned();
+    let description = read_input()?.trim().to_owned();
 
-    Epic {
-        name,
-        description,
-        status: Status::Open,
-        stories: vec![],
-    }
+    Ok(Epic::new(name, description))
 }
 
-fn create_story_prompt() -> Story {
+fn create_story_prompt() -> Result<Story> {
     println!("----------------------------");
     println!("Story Name:");
-    let name = get_user_input().trim().to_owned();
+    let name = read_input()?.trim().to_owned();
     println!("Story Description:");
-    let description = get_user_input().trim().to_owned();
+    let description = read_input()?.trim().to_owned();
 
-    Story {
-        name,
-        description,
-        status: Status::Open,
+    Ok(Story::new(name, description))
+}
+
+pub fn parse_confirmation(input: &str, default_yes: bool) -> bool {
+    match input.trim().to_ascii_lowercase().as_str() {
+        "" => default_yes,
+        "y" | "yes" => true,
+        _ => false,
     }
 }
 
-fn delete_epic_prompt() -> bool {
+fn confirmation_hint(default_yes: bool) -> &'static str {
+    match default_yes {
+        true => "[Y/n]",
+        false => "[y/N]",
+    }
+}
+
+fn delete_epic_prompt(default_yes: bool) -> Result<bool> {
     println!("----------------------------");
-    println!("Are you sure you want to delete this epic? All stories in this epic will also be deleted [Y/n]:");
-    get_user_input().trim().to_ascii_uppercase() == "Y"
+    println!(
+        "Are you sure you want to delete this epic? All stories in this epic will also be deleted {}:",
+        confirmation_hint(default_yes)
+    );
+    Ok(parse_confirmation(&read_input()?, default_yes))
+}
+
+fn delete_epic_keep_stories_prompt(default_yes: bool) -> Result<bool> {
+    println!("----------------------------");
+    println!(
+        "Are you sure you want to delete this epic? Its stories will be kept {}:",
+        confirmation_hint(default_yes)
+    );
+    Ok(parse_confirmation(&read_input()?, default_yes))
+}
+
+fn reassign_stories_prompt() -> Result<Option<u32>> {
+    println!("----------------------------");
+    println!("Move the stories to epic with id (leave empty to keep them without an epic):");
+    Ok(read_input()?.trim().parse::<u32>().ok())
+}
+
+fn merge_into_prompt() -> Result<Option<u32>> {
+    println!("----------------------------");
+    println!("Merge this epic into epic with id (leave empty to cancel):");
+    Ok(read_input()?.trim().parse::<u32>().ok())
+}
+
+fn pick_epic_prompt(epics: &[(u32, String)]) -> Result<Option<u32>> {
+    println!("----------------------------");
+    for (index, (_, name)) in epics.iter().enumerate() {
+        println!("{0: >3}. {1}", index + 1, name);
+    }
+    println!("Number of the epic:");
+    Ok(resolve_epic_choice(epics, &read_input()?))
+}
+
+pub fn resolve_epic_choice(epics: &[(u32, String)], input: &str) -> Option<u32> {
+    let number = input.trim().parse::<usize>().ok()?;
+    epics
+        .get(number.checked_sub(1)?)
+        .map(|(epic_id, _)| *epic_id)
 }
 
-fn delete_story_prompt() -> bool {
+fn delete_story_prompt(default_yes: bool) -> Result<bool> {
     println!("----------------------------");
-    println!("Are you sure you want to delete this story? [Y/n]:");
-    get_user_input().trim().to_ascii_uppercase() == "Y"
+    println!(
+        "Are you sure you want to delete this story? {}:",
+        confirmation_hint(default_yes)
+    );
+    Ok(parse_confirmation(&read_input()?, default_yes))
 }
 
-fn update_status_prompt() -> Option<Status> {
+fn reopen_epic_prompt(closed_stories: usize, default_yes: bool) -> Result<bool> {
+    println!("----------------------------");
+    println!(
+        "This epic is closed and has {} closed stories. Reopen it anyway? {}:",
+        closed_stories,
+        confirmation_hint(default_yes)
+    );
+    Ok(parse_confirmation(&read_input()?, default_yes))
+}
+
+fn close_unchecked_dod_prompt(unchecked: &[&str], default_yes: bool) -> Result<bool> {
+    println!("----------------------------");
+    println!("The definition of done is not met yet:");
+    for name in unchecked {
+        println!("  - {}", name);
+    }
+    println!("Close the epic anyway? {}:", confirmation_hint(default_yes));
+    Ok(parse_confirmation(&read_input()?, default_yes))
+}
+
+fn update_status_prompt() -> Result<Option<Status>> {
     println!("----------------------------");
     println!("New Status (1 - OPEN, 2 - IN-PROGRESS, 3 - RESOLVED, 4 - CLOSED):");
-    let number = get_user_input().trim().parse::<u32>().ok()?;
-    match number {
-        1 => Some(Status::Open),
-        2 => Some(Status::InProgress),
-        3 => Some(Status::Resolved),
-        4 => Some(Status::Closed),
+    read_status()
+}
+
+fn pick_status_prompt(counts: &[(Status, usize)]) -> Result<Option<Status>> {
+    println!("----------------------------");
+    println!("Show stories with status ({}):", status_menu(counts));
+    read_status()
+}
+
+pub fn status_menu(counts: &[(Status, usize)]) -> String {
+    counts
+        .iter()
+        .map(|(status, count)| {
+            let (number, label) = match status {
+                Status::Open => (1, "OPEN"),
+                Status::InProgress => (2, "IN-PROGRESS"),
+                Status::Resolved => (3, "RESOLVED"),
+                Status::Closed => (4, "CLOSED"),
+            };
+            format!("{} - {} ({})", number, label, count)
+        })
+        .join(", ")
+}
+
+fn read_status() -> Result<Option<Status>> {
+    let status = match read_input()?.trim().parse::<u32>() {
+        Ok(1) => Some(Status::Open),
+        Ok(2) => Some(Status::InProgress),
+        Ok(3) => Some(Status::Resolved),
+        Ok(4) => Some(Status::Closed),
         _ => None,
+    };
+    Ok(status)
+}
+
+fn reopen_reason_prompt() -> Result<String> {
+    println!("----------------------------");
+    println!("Reason for reopening:");
+    Ok(read_input()?.trim().to_owned())
+}
+
+fn create_subtask_prompt() -> Result<String> {
+    println!("----------------------------");
+    println!("Subtask Name:");
+    Ok(read_input()?.trim().to_owned())
+}
+
+fn toggle_subtask_prompt() -> Result<Option<usize>> {
+    println!("----------------------------");
+    println!("Number of the subtask to toggle:");
+    read_index()
+}
+
+fn create_dod_item_prompt() -> Result<String> {
+    println!("----------------------------");
+    println!("Definition of done item:");
+    Ok(read_input()?.trim().to_owned())
+}
+
+fn toggle_dod_item_prompt() -> Result<Option<usize>> {
+    println!("----------------------------");
+    println!("Number of the definition of done item to toggle:");
+    read_index()
+}
+
+fn read_index() -> Result<Option<usize>> {
+    let number = read_input()?.trim().parse::<usize>().ok();
+    Ok(number.and_then(|number| number.checked_sub(1)))
+}
+
+fn snooze_until_prompt() -> Result<Option<NaiveDate>> {
+    println!("----------------------------");
+    println!("Snooze until (YYYY-MM-DD, tomorrow, +3d, next monday; leave empty to cancel):");
+    read_date(&read_input()?)
+}
+
+fn due_date_prompt() -> Result<Option<Option<NaiveDate>>> {
+    println!("----------------------------");
+    println!(
+        "Due date (YYYY-MM-DD, tomorrow, +3d, next monday; - to clear, leave empty to cancel):"
+    );
+    match read_input()?.trim() {
+        "-" => Ok(Some(None)),
+        input => Ok(read_date(input)?.map(Some)),
+    }
+}
+
+fn read_date(input: &str) -> Result<Option<NaiveDate>> {
+    if input.trim().is_empty() {
+        return Ok(None);
+    }
+
+    parse_date_input(input, Local::now().date_naive()).map(Some)
+}
+
+fn edit_item_prompt(name: &str, description: &str, status: &Status) -> Result<ItemEdit> {
+    let read_field = || -> Result<Option<String>> {
+        let value = read_input()?.trim().to_owned();
+        Ok((!value.is_empty()).then_some(value))
+    };
+
+    println!("----------------------------");
+    println!("Leave a field empty to keep its current value.");
+    println!("Name [{}]:", name);
+    let name = read_field()?;
+    println!("Description [{}]:", description);
+    let description = read_field()?;
+    println!(
+        "Status [{}] (1 - OPEN, 2 - IN-PROGRESS, 3 - RESOLVED, 4 - CLOSED):",
+        status
+    );
+    let status = read_status()?;
+
+    Ok(ItemEdit {
+        name,
+        description,
+        status,
+    })
+}
+
+fn track_time_prompt() -> Result<(Option<f32>, Option<f32>)> {
+    let read_hours = || -> Result<Option<f32>> { Ok(read_input()?.trim().parse::<f32>().ok()) };
+
+    println!("----------------------------");
+    println!("Leave a field empty to keep its current value.");
+    println!("Estimate (hours):");
+    let estimate_hours = read_hours()?;
+    println!("Actual (hours):");
+    let actual_hours = read_hours()?;
+
+    Ok((estimate_hours, actual_hours))
+}
+
+fn assign_prompt() -> Result<String> {
+    println!("----------------------------");
+    println!("Assignee to add (prefix with - to remove):");
+    Ok(read_input()?.trim().to_owned())
+}
+
+fn replace_text_prompt() -> Result<Option<(String, String, TextFields, bool)>> {
+    println!("----------------------------");
+    println!("Find (leave empty to cancel):");
+    let find = read_input()?.trim().to_owned();
+    if find.is_empty() {
+        return Ok(None);
+    }
+    println!("Replace with:");
+    let replace = read_input()?.trim().to_owned();
+    println!("In (1 - names, 2 - descriptions, 3 - both) [3]:");
+    let fields = match read_input()?.trim() {
+        "1" => TextFields {
+            names: true,
+            descriptions: false,
+        },
+        "2" => TextFields {
+            names: false,
+            descriptions: true,
+        },
+        _ => TextFields {
+            names: true,
+            descriptions: true,
+        },
+    };
+    println!("Match case? (y/N):");
+    let case_sensitive = read_input()?.trim().eq_ignore_ascii_case("y");
+
+    Ok(Some((find, replace, fields, case_sensitive)))
+}
+
+fn percent_complete_prompt() -> Result<Option<u8>> {
+    println!("----------------------------");
+    println!("Percent complete (0-100, leave empty to cancel):");
+    Ok(read_input()?.trim().parse::<u8>().ok())
+}
+
+fn goto_prompt() -> Result<String> {
+    println!("----------------------------");
+    println!("Go to (id or part of a name):");
+    Ok(read_input()?.trim().to_owned())
+}
+
+fn quick_add_prompt() -> Result<String> {
+    println!("----------------------------");
+    println!("Quick add (\"name\" under <epic id> @assignee):");
+    Ok(read_input()?.trim().to_owned())
+}
+
+fn read_input() -> Result<String> {
+    Ok(get_user_input().ok_or(JiraError::InputClosed)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_confirmation_should_use_default_on_empty_input() {
+        assert!(parse_confirmation("", true));
+        assert!(parse_confirmation("\n", true));
+        assert!(!parse_confirmation("", false));
+        assert!(!parse_confirmation("\n", false));
+    }
+
+    #[test]
+    fn resolve_epic_choice_should_map_numbers_to_epic_ids() {
+        let epics = vec![(3, "first".to_owned()), (7, "second".to_owned())];
+
+        assert_eq!(resolve_epic_choice(&epics, "1"), Some(3));
+        assert_eq!(resolve_epic_choice(&epics, "2\n"), Some(7));
+        assert_eq!(resolve_epic_choice(&epics, "0"), None);
+        assert_eq!(resolve_epic_choice(&epics, "3"), None);
+        assert_eq!(resolve_epic_choice(&epics, "first"), None);
+    }
+
+    #[test]
+    fn status_menu_should_show_counts() {
+        let counts = vec![
+            (Status::Open, 2),
+            (Status::InProgress, 4),
+            (Status::Resolved, 0),
+            (Status::Closed, 1),
+        ];
+
+        assert_eq!(
+            status_menu(&counts),
+            "1 - OPEN (2), 2 - IN-PROGRESS (4), 3 - RESOLVED (0), 4 - CLOSED (1)"
+        );
+    }
+
+    #[test]
+    fn parse_confirmation_should_respect_explicit_answers() {
+        for default_yes in [true, false] {
+            assert!(parse_confirmation("y", default_yes));
+            assert!(parse_confirmation("Y\n", default_yes));
+            assert!(parse_confirmation("yes", default_yes));
+            assert!(!parse_confirmation("n", default_yes));
+            assert!(!parse_confirmation("nope", default_yes));
+        }
     }
 }
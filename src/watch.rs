@@ -0,0 +1,76 @@
+use crate::models::{DBState, Status, Story};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum WatchEvent {
+    Unchanged,
+    Changed,
+    Closed,
+    Deleted,
+}
+
+pub fn watch_event(previous: &DBState, current: &DBState, epic_id: u32) -> WatchEvent {
+    let Some(epic) = current.epics.get(&epic_id) else {
+        return WatchEvent::Deleted;
+    };
+    let before = previous.epics.get(&epic_id);
+    if epic.status == Status::Closed && before.is_none_or(|before| before.status != Status::Closed)
+    {
+        return WatchEvent::Closed;
+    }
+
+    let stories = |state: &DBState| -> Vec<Option<Story>> {
+        state.epics[&epic_id]
+            .stories
+            .iter()
+            .map(|story_id| state.stories.get(story_id).cloned())
+            .collect()
+    };
+    match before == Some(epic) && stories(previous) == stories(current) {
+        true => WatchEvent::Unchanged,
+        false => WatchEvent::Changed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::models::Epic;
+
+    fn board(status: Status, story_name: &str) -> DBState {
+        let mut epic = Epic::new("Epic".to_owned(), "".to_owned());
+        epic.status = status;
+        epic.stories = vec![2];
+
+        DBState {
+            last_item_id: 2,
+            epics: HashMap::from([(1, epic)]),
+            stories: HashMap::from([(2, Story::new(story_name.to_owned(), "".to_owned()))]),
+            project_name: None,
+            project_version: None,
+        }
+    }
+
+    #[test]
+    fn watch_event_should_detect_epic_transitions() {
+        let open = board(Status::InProgress, "First");
+        let renamed = DBState {
+            stories: board(Status::InProgress, "Renamed").stories,
+            ..open.clone()
+        };
+        let closed = DBState {
+            epics: board(Status::Closed, "First").epics,
+            ..open.clone()
+        };
+
+        assert_eq!(watch_event(&open, &open, 1), WatchEvent::Unchanged);
+        assert_eq!(watch_event(&open, &renamed, 1), WatchEvent::Changed);
+        assert_eq!(watch_event(&open, &closed, 1), WatchEvent::Closed);
+        assert_eq!(watch_event(&closed, &closed, 1), WatchEvent::Unchanged);
+        assert_eq!(
+            watch_event(&open, &DBState::default(), 1),
+            WatchEvent::Deleted
+        );
+    }
+}